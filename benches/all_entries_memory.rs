@@ -0,0 +1,101 @@
+//! Measures the peak memory of deserializing an `all` response by buffering the whole body first,
+//! as `Response::json` does, against deserializing it while it's read, as the blocking client does for bulk requests.
+//!
+//! Run with `cargo bench --bench all_entries_memory`, the fixture's size can be changed with the `ENTRIES` environment variable.
+use rusty_hyrule_compendium::domain::responses::AllStandardEntries;
+use serde::Deserialize;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::io::{BufReader, Read};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The system allocator, tracking the bytes currently allocated and the most allocated at once
+struct PeakAllocator;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for PeakAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let pointer = System.alloc(layout);
+        if !pointer.is_null() {
+            let allocated = ALLOCATED.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+            PEAK.fetch_max(allocated, Ordering::SeqCst);
+        }
+        pointer
+    }
+
+    unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout) {
+        System.dealloc(pointer, layout);
+        ALLOCATED.fetch_sub(layout.size(), Ordering::SeqCst);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: PeakAllocator = PeakAllocator;
+
+#[derive(Deserialize)]
+struct ApiResponse {
+    data: AllStandardEntries,
+}
+
+/// Reads the fixture in chunks the size of a typical network read, as a response body would be
+struct ChunkedBody<'a>(&'a [u8]);
+
+impl Read for ChunkedBody<'_> {
+    fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+        let length = buffer.len().min(16 * 1024).min(self.0.len());
+        buffer[..length].copy_from_slice(&self.0[..length]);
+        self.0 = &self.0[length..];
+        Ok(length)
+    }
+}
+
+fn fixture(entries: usize) -> Vec<u8> {
+    let monsters: Vec<String> = (0..entries)
+        .map(|id| {
+            format!(
+                r#"{{"category":"monsters","common_locations":["Hyrule Field","Great Hyrule Forest"],"description":"The strongest of all Moblins, Ganon's fiendish magic has allowed them to surpass even the Black Moblins in strength and resilience.","drops":["moblin horn","moblin fang","moblin guts","amber","opal"],"id":{},"image":"https://botw-compendium.herokuapp.com/api/v2/entry/silver_moblin/image","name":"silver moblin {}"}}"#,
+                id, id
+            )
+        })
+        .collect();
+    format!(
+        r#"{{"data":{{"creatures":{{"food":[],"non_food":[]}},"equipment":[],"materials":[],"monsters":[{}],"treasure":[]}}}}"#,
+        monsters.join(",")
+    )
+    .into_bytes()
+}
+
+/// The most bytes allocated at once while running `parse`, beyond those allocated before it started
+fn peak_allocated(parse: impl FnOnce() -> AllStandardEntries) -> usize {
+    let baseline = ALLOCATED.load(Ordering::SeqCst);
+    PEAK.store(baseline, Ordering::SeqCst);
+    let entries = parse();
+    let peak = PEAK.load(Ordering::SeqCst) - baseline;
+    drop(entries);
+    peak
+}
+
+fn main() {
+    let entries = std::env::var("ENTRIES")
+        .ok()
+        .and_then(|entries| entries.parse().ok())
+        .unwrap_or(20_000);
+    let body = fixture(entries);
+
+    let buffered = peak_allocated(|| {
+        let mut buffer = Vec::new();
+        ChunkedBody(&body).read_to_end(&mut buffer).unwrap();
+        serde_json::from_slice::<ApiResponse>(&buffer).unwrap().data
+    });
+    let streamed = peak_allocated(|| {
+        serde_json::from_reader::<_, ApiResponse>(BufReader::new(ChunkedBody(&body)))
+            .unwrap()
+            .data
+    });
+
+    let mebibytes = |bytes: usize| bytes as f64 / (1024.0 * 1024.0);
+    println!("{} entries, {:.1} MiB body", entries, mebibytes(body.len()));
+    println!("buffered peak: {:.1} MiB", mebibytes(buffered));
+    println!("streamed peak: {:.1} MiB", mebibytes(streamed));
+}
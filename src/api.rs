@@ -0,0 +1,396 @@
+//! The shapes and checks shared by the compendium clients when requesting data from the API
+use crate::domain::inputs::{normalize_name, CompendiumCategory, EntryIdentifier, GameMode};
+use crate::domain::models::{normalize_for_comparison, CompendiumEntry};
+#[cfg(feature = "creatures")]
+use crate::domain::responses::AllCreatureEntries;
+use crate::domain::responses::{AllStandardEntries, CategoryResult};
+use crate::error::CompendiumError;
+use crate::result::Result;
+use reqwest::{StatusCode, Url};
+use serde::de::value::{MapAccessDeserializer, SeqAccessDeserializer};
+use serde::de::{
+    DeserializeOwned, DeserializeSeed, IntoDeserializer, MapAccess, SeqAccess, Visitor,
+};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::borrow::Cow;
+use std::collections::{BTreeSet, HashSet};
+use std::fmt;
+use std::marker::PhantomData;
+
+/// The maximum number of pages followed while requesting a paginated resource
+pub(crate) const MAX_PAGES: usize = 50;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub(crate) struct ApiResponse<T> {
+    pub(crate) data: T,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) next: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) message: Option<String>,
+}
+
+impl ApiResponse<serde_json::Value> {
+    /// Deserialize the data into `T`, or return [NoDataFound](CompendiumError::NoDataFound) for the url when the API
+    /// responded successfully but without any results, i.e. `{"data":{},"message":"no results"}`
+    pub(crate) fn into_data<T: DeserializeOwned>(self, url: &Url) -> Result<T> {
+        if self.is_no_results() {
+            return Err(CompendiumError::NoDataFound(url.path().to_string()));
+        }
+        serde_json::from_value(self.data).map_err(CompendiumError::DeserializationError)
+    }
+
+    fn is_no_results(&self) -> bool {
+        self.message.as_deref() == Some(NO_RESULTS_MESSAGE)
+            || self.data.as_object().is_some_and(|data| data.is_empty())
+    }
+}
+
+impl<T> ApiResponse<MaybeEmpty<T>> {
+    /// The response with its data, or [NoDataFound](CompendiumError::NoDataFound) for the url when the API
+    /// responded successfully but without any results, i.e. `{"data":{},"message":"no results"}`
+    pub(crate) fn into_found(self, url: &Url) -> Result<ApiResponse<T>> {
+        match self.data {
+            MaybeEmpty::Data(data) if self.message.as_deref() != Some(NO_RESULTS_MESSAGE) => {
+                Ok(ApiResponse {
+                    data,
+                    next: self.next,
+                    message: self.message,
+                })
+            }
+            _ => Err(CompendiumError::NoDataFound(url.path().to_string())),
+        }
+    }
+}
+
+/// The message the API responds with, alongside empty data, when nothing matched the request
+pub(crate) const NO_RESULTS_MESSAGE: &str = "no results";
+
+/// Data deserialized as it's read, which is empty (`{}`) when the API responded successfully without any results.
+/// As the data isn't buffered, whether it's empty is checked as it's deserialized rather than beforehand.
+pub(crate) enum MaybeEmpty<T> {
+    Empty,
+    Data(T),
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for MaybeEmpty<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        deserializer.deserialize_any(MaybeEmptyVisitor(PhantomData))
+    }
+}
+
+struct MaybeEmptyVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for MaybeEmptyVisitor<T> {
+    type Value = MaybeEmpty<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("the response's data")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> std::result::Result<Self::Value, A::Error> {
+        T::deserialize(SeqAccessDeserializer::new(seq)).map(MaybeEmpty::Data)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(
+        self,
+        mut map: A,
+    ) -> std::result::Result<Self::Value, A::Error> {
+        match map.next_key::<String>()? {
+            None => Ok(MaybeEmpty::Empty),
+            Some(first_key) => T::deserialize(MapAccessDeserializer::new(PrefixedMap {
+                first_key: Some(first_key),
+                map,
+            }))
+            .map(MaybeEmpty::Data),
+        }
+    }
+}
+
+/// A map whose first key has already been read, e.g. to check the map isn't empty, giving that key back before the rest of the map
+struct PrefixedMap<A> {
+    first_key: Option<String>,
+    map: A,
+}
+
+impl<'de, A: MapAccess<'de>> MapAccess<'de> for PrefixedMap<A> {
+    type Error = A::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> std::result::Result<Option<K::Value>, A::Error> {
+        match self.first_key.take() {
+            Some(key) => seed
+                .deserialize(IntoDeserializer::<A::Error>::into_deserializer(key))
+                .map(Some),
+            None => self.map.next_key_seed(seed),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> std::result::Result<V::Value, A::Error> {
+        self.map.next_value_seed(seed)
+    }
+}
+
+/// Parse the base url of the API, appending a trailing slash when it's missing.
+/// Resources are joined onto the base url, which replaces its last path segment unless it ends with a slash,
+/// so without one e.g. `https://host/api/v2` would silently request `https://host/api/entry/1`.
+pub(crate) fn parse_base_url(url: &str) -> Result<Url> {
+    let mut base_url =
+        Url::parse(url).map_err(|_e| CompendiumError::InvalidBaseUrl(url.to_string()))?;
+    if !base_url.path().ends_with('/') {
+        let path = format!("{}/", base_url.path());
+        base_url.set_path(&path);
+    }
+    Ok(base_url)
+}
+
+/// Deserialize the data from JSON in the shape the API responds with, i.e. wrapped in `{"data": ...}`
+pub(crate) fn data_from_json_str<T: DeserializeOwned>(json: &str) -> Result<T> {
+    serde_json::from_str::<ApiResponse<T>>(json)
+        .map(|api_response| api_response.data)
+        .map_err(CompendiumError::DeserializationError)
+}
+
+/// Data that the API may split across multiple pages, joined back together as each page is requested
+pub(crate) trait Paginated {
+    fn append_page(&mut self, page: Self);
+}
+
+impl<T> Paginated for Vec<T> {
+    fn append_page(&mut self, mut page: Self) {
+        self.append(&mut page)
+    }
+}
+
+/// A list of entries that some forks of the API return as a bare object when there's only one entry.
+/// Both `{...}` and `[{...}]` deserialize to a list.
+#[cfg(any(
+    feature = "equipment",
+    feature = "materials",
+    feature = "monsters",
+    feature = "treasure"
+))]
+#[derive(Clone, Debug)]
+pub(crate) struct OneOrMany<T>(Vec<T>);
+
+#[cfg(any(
+    feature = "equipment",
+    feature = "materials",
+    feature = "monsters",
+    feature = "treasure"
+))]
+impl<T> OneOrMany<T> {
+    pub(crate) fn into_vec(self) -> Vec<T> {
+        self.0
+    }
+}
+
+#[cfg(any(
+    feature = "equipment",
+    feature = "materials",
+    feature = "monsters",
+    feature = "treasure"
+))]
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for OneOrMany<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        deserialize_one_or_many(deserializer).map(OneOrMany)
+    }
+}
+
+#[cfg(any(
+    feature = "equipment",
+    feature = "materials",
+    feature = "monsters",
+    feature = "treasure"
+))]
+impl<T> Paginated for OneOrMany<T> {
+    fn append_page(&mut self, page: Self) {
+        self.0.append_page(page.0)
+    }
+}
+
+/// Deserialize either a single entry or a list of entries into a list, see [OneOrMany]
+pub(crate) fn deserialize_one_or_many<'de, D, T>(
+    deserializer: D,
+) -> std::result::Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Shape<T> {
+        Many(Vec<T>),
+        One(T),
+    }
+
+    match Shape::deserialize(deserializer)? {
+        Shape::Many(entries) => Ok(entries),
+        Shape::One(entry) => Ok(vec![entry]),
+    }
+}
+
+#[cfg(feature = "creatures")]
+impl Paginated for AllCreatureEntries {
+    fn append_page(&mut self, mut page: Self) {
+        self.food_mut().append(page.food_mut());
+        self.non_food_mut().append(page.non_food_mut());
+    }
+}
+
+impl Paginated for AllStandardEntries {
+    fn append_page(&mut self, mut page: Self) {
+        #[cfg(feature = "creatures")]
+        self.creatures_mut()
+            .append_page(std::mem::take(page.creatures_mut()));
+        #[cfg(feature = "equipment")]
+        self.equipment_mut().append(page.equipment_mut());
+        #[cfg(feature = "materials")]
+        self.materials_mut().append(page.materials_mut());
+        #[cfg(feature = "monsters")]
+        self.monsters_mut().append(page.monsters_mut());
+        #[cfg(feature = "treasure")]
+        self.treasure_mut().append(page.treasure_mut());
+    }
+}
+
+pub(crate) fn entry_path(identifier: &EntryIdentifier, mode: GameMode) -> String {
+    let entry_identifier = match identifier {
+        EntryIdentifier::Id(id) => id.to_string(),
+        EntryIdentifier::Name(name) => normalize_name(name),
+        EntryIdentifier::OwnedName(name) => normalize_name(name),
+    };
+    if mode == GameMode::MasterMode {
+        return format!("master_mode/entry/{}", entry_identifier);
+    }
+    format!("entry/{}", entry_identifier)
+}
+
+pub(crate) fn category_path(category: CompendiumCategory, mode: GameMode) -> String {
+    let category_path = format!("category/{}", category_path_for_type(&category));
+    if mode == GameMode::MasterMode {
+        return format!("master_mode/{}", category_path);
+    }
+    category_path
+}
+
+pub(crate) fn category_path_for_type(category: &CompendiumCategory) -> &'static str {
+    category.api_name()
+}
+
+pub(crate) fn check_status(status_code: StatusCode, url: &Url) -> Result<()> {
+    // Would response_data.error_for_status() be better?
+    if status_code.is_server_error() {
+        return Err(CompendiumError::ServerError);
+    }
+    if status_code == StatusCode::NOT_FOUND {
+        return Err(CompendiumError::NoDataFound(url.path().to_string()));
+    }
+    if status_code.is_client_error() {
+        return Err(CompendiumError::ClientError {
+            status: status_code.as_u16(),
+            path: url.path().to_string(),
+        });
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_category_types(
+    expected: &str,
+    category_result: &CategoryResult,
+) -> Result<()> {
+    match category_result
+        .entries()
+        .find(|entry| entry.category_type() != expected)
+    {
+        Some(entry) => Err(CompendiumError::CategoryMismatch {
+            expected: expected.to_string(),
+            found: entry.category_type().to_string(),
+        }),
+        None => Ok(()),
+    }
+}
+
+pub(crate) fn validate_unique_ids<'a, E>(entries: impl IntoIterator<Item = &'a E>) -> Result<()>
+where
+    E: CompendiumEntry + ?Sized + 'a,
+{
+    let mut ids = HashSet::new();
+    match entries.into_iter().find(|entry| !ids.insert(entry.id())) {
+        Some(entry) => Err(CompendiumError::DuplicateId(entry.id())),
+        None => Ok(()),
+    }
+}
+
+pub(crate) fn validate_all_entries_unique_ids(all_entries: &AllStandardEntries) -> Result<()> {
+    #[cfg(feature = "creatures")]
+    {
+        let creatures = all_entries.creatures();
+        validate_unique_ids(creatures.food().iter().chain(creatures.non_food()))?;
+    }
+    #[cfg(feature = "equipment")]
+    validate_unique_ids(all_entries.equipment())?;
+    #[cfg(feature = "materials")]
+    validate_unique_ids(all_entries.materials())?;
+    #[cfg(feature = "monsters")]
+    validate_unique_ids(all_entries.monsters())?;
+    #[cfg(feature = "treasure")]
+    validate_unique_ids(all_entries.treasure())?;
+    Ok(())
+}
+
+/// The ids of entries in the category result but missing from all entries' category, or vice versa, sorted by id
+pub(crate) fn category_discrepancies(
+    category_result: &CategoryResult,
+    all_entries: &AllStandardEntries,
+    category: CompendiumCategory,
+) -> Vec<i32> {
+    let category_ids: BTreeSet<i32> = category_result.entries().map(|entry| entry.id()).collect();
+    let all_ids: BTreeSet<i32> = all_entries
+        .category_entries(category)
+        .map(|entry| entry.id())
+        .collect();
+    category_ids
+        .symmetric_difference(&all_ids)
+        .copied()
+        .collect()
+}
+
+/// A requested name normalized to compare with entries' normalized names, with underscores as spaces as in the API's paths
+fn name_for_comparison(name: &str) -> String {
+    normalize_for_comparison(&name.replace('_', " ")).into_owned()
+}
+
+/// The 1-based position of the matching entry when every entry is sorted by id, along with the total number of entries.
+/// Names are matched as they are when requesting an entry, so e.g. "silver_moblin" and "Silver Moblin" both match "silver moblin".
+pub(crate) fn rank_entry(
+    all_entries: &AllStandardEntries,
+    identifier: EntryIdentifier,
+) -> Result<(usize, usize)> {
+    let mut ranked: Vec<(i32, Cow<str>)> = all_entries
+        .entries()
+        .map(|entry| (entry.id(), entry.name_normalized()))
+        .collect();
+    ranked.sort_unstable();
+    let expected_name = match &identifier {
+        EntryIdentifier::Id(_) => None,
+        EntryIdentifier::Name(name) => Some(name_for_comparison(name)),
+        EntryIdentifier::OwnedName(name) => Some(name_for_comparison(name)),
+    };
+    ranked
+        .iter()
+        .position(|(id, name)| match &identifier {
+            EntryIdentifier::Id(expected) => id == expected,
+            _ => expected_name.as_deref() == Some(name.as_ref()),
+        })
+        .map(|position| (position + 1, ranked.len()))
+        .ok_or_else(|| {
+            // The same shape of path as a miss when requesting the entry, see `ApiResponse::into_data`
+            let path = format!("/{}", entry_path(&identifier, GameMode::Standard));
+            CompendiumError::NoDataFound(path)
+        })
+}
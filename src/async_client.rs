@@ -0,0 +1,27 @@
+//! Async module
+//!
+//! Every future returned by the async client is cancel-safe: dropping it before it completes cancels the in-flight request,
+//! closing its connection, without leaving any partially applied state behind in the client.
+//!
+//! The async client is only available with the `async` feature, it has the same methods as the
+//! [blocking client](crate::blocking::CompendiumApiClient) but each returns a future.
+//! ```rust,no_run
+//! use rusty_hyrule_compendium::async_client::CompendiumClient;
+//! use rusty_hyrule_compendium::domain::inputs::EntryIdentifier;
+//! use rusty_hyrule_compendium::Result;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<()> {
+//!     // Preconfigured client using v2 of the API
+//!     let client = CompendiumClient::default();
+//!     let monster_entry = client.monster(EntryIdentifier::Id(123)).await?;
+//!     // "white-maned lynel"
+//!     let monster_name = monster_entry.name();
+//!     Ok(())
+//! }
+//! ```
+
+/// The async compendium client
+mod compendium;
+
+pub use compendium::*;
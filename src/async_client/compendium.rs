@@ -0,0 +1,508 @@
+#[cfg(any(
+    feature = "equipment",
+    feature = "materials",
+    feature = "monsters",
+    feature = "treasure"
+))]
+use crate::api::OneOrMany;
+use crate::api::{
+    category_discrepancies, category_path, category_path_for_type, check_status, entry_path,
+    parse_base_url, rank_entry, validate_all_entries_unique_ids, validate_category_types,
+    validate_unique_ids, ApiResponse, MaybeEmpty, Paginated, MAX_PAGES,
+};
+use crate::config::{ClientConfig, DEFAULT_BASE_URL, DEFAULT_USER_AGENT};
+use crate::domain::inputs::{CompendiumCategory, EntryIdentifier, FailurePolicy, GameMode};
+#[cfg(feature = "creatures")]
+use crate::domain::models::CreatureEntry;
+use crate::domain::models::EntrySummary;
+#[cfg(feature = "equipment")]
+use crate::domain::models::EquipmentEntry;
+#[cfg(feature = "materials")]
+use crate::domain::models::MaterialEntry;
+#[cfg(feature = "monsters")]
+use crate::domain::models::MonsterEntry;
+#[cfg(feature = "treasure")]
+use crate::domain::models::TreasureEntry;
+use crate::domain::responses::{AllStandardEntries, CategoryCounts, CategoryResult, EntryResponse};
+use crate::error::CompendiumError;
+use crate::result::Result;
+use reqwest::{Client, Response, Url};
+use serde::de::DeserializeOwned;
+use std::time::Duration;
+
+/// The async CompendiumClient that can be used to obtain relevant entries.
+/// Dropping any of the returned futures cancels the request being made.
+#[derive(Debug, Clone)]
+pub struct CompendiumClient {
+    base_url: Url,
+    network_client: Client,
+    config: ClientConfig,
+}
+
+impl Default for CompendiumClient {
+    fn default() -> CompendiumClient {
+        CompendiumClient {
+            base_url: Url::parse(DEFAULT_BASE_URL).unwrap(),
+            network_client: default_network_client(),
+            config: ClientConfig::default(),
+        }
+    }
+}
+
+impl CompendiumClient {
+    /// A convience method to initialise a compendium client if the CompendiumClient::Default() isn't sufficient.
+    /// A trailing slash is appended to the url when it's missing, see [base_url](crate::blocking::CompendiumClientBuilder::base_url)
+    pub fn new(url: &str) -> Result<Self> {
+        Ok(CompendiumClient {
+            base_url: parse_base_url(url)?,
+            network_client: default_network_client(),
+            config: ClientConfig {
+                base_url: url.to_string(),
+                ..ClientConfig::default()
+            },
+        })
+    }
+
+    /// The base url of the API the client requests data from
+    pub fn base_url(&self) -> &Url {
+        &self.base_url
+    }
+
+    /// The configuration the client was built with
+    pub fn config(&self) -> &ClientConfig {
+        &self.config
+    }
+
+    /// Get an entry (see [EntryResponse](crate::domain::responses::EntryResponse) for exact types that can be returned) by [identifier](crate::domain::inputs::EntryIdentifier)
+    pub async fn entry(&self, identifier: EntryIdentifier<'_>) -> Result<EntryResponse> {
+        self.fetch_data_for_specified_entry(identifier, GameMode::Standard)
+            .await
+    }
+
+    /// Get an entry by a primary [identifier](crate::domain::inputs::EntryIdentifier), falling back to a second identifier when no data is found for the first.
+    /// Only a [NoDataFound](crate::CompendiumError::NoDataFound) error triggers the fallback, any other error is returned straight away.
+    pub async fn entry_with_fallback(
+        &self,
+        primary: EntryIdentifier<'_>,
+        fallback: EntryIdentifier<'_>,
+    ) -> Result<EntryResponse> {
+        match self.entry(primary).await {
+            Err(CompendiumError::NoDataFound(_)) => self.entry(fallback).await,
+            result => result,
+        }
+    }
+
+    /// Get a lightweight [summary](crate::domain::models::EntrySummary) of an entry by [identifier](crate::domain::inputs::EntryIdentifier)
+    pub async fn entry_summary(&self, identifier: EntryIdentifier<'_>) -> Result<EntrySummary> {
+        self.entry(identifier)
+            .await
+            .map(|entry| EntrySummary::from(&entry))
+    }
+
+    /// Get a [monster entry](crate::domain::models::MonsterEntry) by [identifier](crate::domain::inputs::EntryIdentifier)
+    #[cfg(feature = "monsters")]
+    pub async fn monster(&self, identifier: EntryIdentifier<'_>) -> Result<MonsterEntry> {
+        self.fetch_data_for_specified_entry(identifier, GameMode::Standard)
+            .await
+    }
+
+    /// Get specifically a [monster entry](crate::domain::models::MonsterEntry) that exists only in master mode by [identifier](crate::domain::inputs::EntryIdentifier)
+    #[cfg(feature = "monsters")]
+    pub async fn master_mode_monster(
+        &self,
+        identifier: EntryIdentifier<'_>,
+    ) -> Result<MonsterEntry> {
+        self.fetch_data_for_specified_entry(identifier, GameMode::MasterMode)
+            .await
+    }
+
+    /// Get specifically a [treasure entry](crate::domain::models::TreasureEntry) by [identifier](crate::domain::inputs::EntryIdentifier)
+    #[cfg(feature = "treasure")]
+    pub async fn treasure(&self, identifier: EntryIdentifier<'_>) -> Result<TreasureEntry> {
+        self.fetch_data_for_specified_entry(identifier, GameMode::Standard)
+            .await
+    }
+
+    /// Get specifically a [creature entry](crate::domain::models::CreatureEntry) by [identifier](crate::domain::inputs::EntryIdentifier)
+    #[cfg(feature = "creatures")]
+    pub async fn creature(&self, identifier: EntryIdentifier<'_>) -> Result<CreatureEntry> {
+        self.fetch_data_for_specified_entry(identifier, GameMode::Standard)
+            .await
+    }
+
+    /// Get specifically a [material entry](crate::domain::models::MaterialEntry) by [identifier](crate::domain::inputs::EntryIdentifier)
+    #[cfg(feature = "materials")]
+    pub async fn material(&self, identifier: EntryIdentifier<'_>) -> Result<MaterialEntry> {
+        self.fetch_data_for_specified_entry(identifier, GameMode::Standard)
+            .await
+    }
+
+    /// Get specifically an [equipment entry](crate::domain::models::EquipmentEntry) by [identifier](crate::domain::inputs::EntryIdentifier)
+    #[cfg(feature = "equipment")]
+    pub async fn equipment(&self, identifier: EntryIdentifier<'_>) -> Result<EquipmentEntry> {
+        self.fetch_data_for_specified_entry(identifier, GameMode::Standard)
+            .await
+    }
+
+    /// Get all entries for a given a category
+    pub async fn category(&self, category: CompendiumCategory) -> Result<CategoryResult> {
+        let category_url =
+            self.create_path(&self.base_url, category_path(category, GameMode::Standard))?;
+        self.fetch_data_for_specific_category(category_url, category)
+            .await
+    }
+
+    /// Get all master mode entries for a given category.
+    /// Master mode only adds monsters, so the API's response for any other category is returned as is, which is typically no entries.
+    pub async fn master_mode_category(
+        &self,
+        category: CompendiumCategory,
+    ) -> Result<CategoryResult> {
+        let category_url = self.create_path(
+            &self.base_url,
+            category_path(category, GameMode::MasterMode),
+        )?;
+        self.fetch_data_for_specific_category(category_url, category)
+            .await
+    }
+
+    /// Get all entries in the compendium (excluding master mode)
+    pub async fn all_entries(&self) -> Result<AllStandardEntries> {
+        let all_normal_mode_entries_url = self.create_path(&self.base_url, "all")?;
+        let all_entries: AllStandardEntries = self
+            .fetch_paginated_data(all_normal_mode_entries_url, self.config.bulk_timeout)
+            .await?;
+        if self.config.detect_duplicate_ids {
+            validate_all_entries_unique_ids(&all_entries)?;
+        }
+        Ok(all_entries)
+    }
+
+    /// Get all entries in the compendium by requesting each category in turn, rather than requesting all entries at once.
+    /// With [CollectErrors](crate::domain::inputs::FailurePolicy::CollectErrors) the categories that failed are left empty and their errors are returned alongside,
+    /// with [AbortOnFirst](crate::domain::inputs::FailurePolicy::AbortOnFirst) the first error is returned instead.
+    pub async fn all_entries_via_categories(
+        &self,
+        policy: FailurePolicy,
+    ) -> Result<(AllStandardEntries, Vec<CompendiumError>)> {
+        let mut all_entries = AllStandardEntries::default();
+        let mut errors = Vec::new();
+        for category in CompendiumCategory::ALL {
+            match self.category(*category).await {
+                Ok(category_result) => all_entries.replace_category(category_result),
+                Err(error) if policy == FailurePolicy::CollectErrors => errors.push(error),
+                Err(error) => return Err(error),
+            }
+        }
+        Ok((all_entries, errors))
+    }
+
+    /// Get the ids of entries that are returned for the category but missing from all entries, or vice versa, sorted by id.
+    /// This requests both the category and all entries so is useful for checking the API's consistency rather than routine use.
+    pub async fn category_vs_all_discrepancies(
+        &self,
+        category: CompendiumCategory,
+    ) -> Result<Vec<i32>> {
+        Ok(category_discrepancies(
+            &self.category(category).await?,
+            &self.all_entries().await?,
+            category,
+        ))
+    }
+
+    /// Find the entries whose name or description contains the query, ignoring case, with name matches first.
+    /// This downloads every entry in the compendium for each search, so consider requesting [all_entries](Self::all_entries) once
+    /// and using [search](crate::domain::responses::AllStandardEntries::search) on them when searching repeatedly.
+    pub async fn search(&self, query: &str) -> Result<Vec<EntryResponse>> {
+        Ok(self.all_entries().await?.search(query))
+    }
+
+    /// Get the number of entries in each category, e.g. for a dashboard.
+    /// The API has no way of counting entries, so this requests all entries and counts them: it costs as much as [all_entries](Self::all_entries),
+    /// but is still a single request rather than one per category.
+    pub async fn category_counts(&self) -> Result<CategoryCounts> {
+        Ok(self.all_entries().await?.category_counts())
+    }
+
+    /// Get the 1-based position of an entry when every entry is sorted by id, along with the total number of entries, e.g. for showing "112 of 389".
+    /// This requests all entries so the ranking is over the standard dataset, master mode entries aren't included.
+    /// Names are matched ignoring case and a [NoDataFound](crate::CompendiumError::NoDataFound) error is returned when no entry matches.
+    pub async fn entry_rank(&self, identifier: EntryIdentifier<'_>) -> Result<(usize, usize)> {
+        rank_entry(&self.all_entries().await?, identifier)
+    }
+
+    /// Get all [master mode entries](crate::domain::models::MonsterEntry) (which are only monsters) in the compendium
+    #[cfg(feature = "monsters")]
+    pub async fn all_master_mode_entries(&self) -> Result<Vec<MonsterEntry>> {
+        let all_master_mode_entries_url = self.create_path(&self.base_url, "master_mode/all")?;
+        self.fetch_paginated_data(all_master_mode_entries_url, self.config.bulk_timeout)
+            .await
+    }
+
+    fn create_path<S: Into<String>>(&self, url: &Url, path_to_add: S) -> Result<Url> {
+        url.join(path_to_add.into().as_str())
+            .map_err(|_e| CompendiumError::ErrorConstructingResourceUrl)
+    }
+
+    async fn make_request(&self, url: Url, timeout: Option<Duration>) -> Result<Response> {
+        #[cfg(feature = "tracing")]
+        let span = crate::instrumentation::request_span(&url);
+        #[cfg(feature = "tracing")]
+        span.in_scope(|| crate::instrumentation::trace_request_sent(&url));
+        let request = self.network_client.get(url);
+        let request = match timeout {
+            Some(timeout) => request.timeout(timeout),
+            None => request,
+        };
+        #[cfg(any(feature = "metrics", feature = "tracing"))]
+        let started = std::time::Instant::now();
+        let sent = request.send();
+        #[cfg(feature = "tracing")]
+        let sent = tracing::Instrument::instrument(sent, span.clone());
+        let result = match sent.await {
+            Ok(response) => check_status(response.status(), response.url()).map(|_| response),
+            Err(error) => Err(CompendiumError::from_request_error(error)),
+        };
+        #[cfg(feature = "metrics")]
+        crate::instrumentation::record_request(started, &result);
+        #[cfg(feature = "tracing")]
+        span.in_scope(|| {
+            crate::instrumentation::trace_response(started, &result, |response| {
+                response.status().as_u16()
+            })
+        });
+        result
+    }
+
+    async fn fetch_api_response<T>(
+        &self,
+        url: Url,
+        timeout: Option<Duration>,
+    ) -> Result<ApiResponse<T>>
+    where
+        T: DeserializeOwned,
+    {
+        let response = self.make_request(url, timeout).await?;
+        response
+            .json::<ApiResponse<T>>()
+            .await
+            .map_err(CompendiumError::ResponseParsingError)
+    }
+
+    /// Requests a response whose data may be empty, returning a [NoDataFound](CompendiumError::NoDataFound) error when the API responded without any results
+    async fn fetch_found_api_response<T>(
+        &self,
+        url: Url,
+        timeout: Option<Duration>,
+    ) -> Result<ApiResponse<T>>
+    where
+        T: DeserializeOwned,
+    {
+        self.fetch_api_response::<MaybeEmpty<T>>(url.clone(), timeout)
+            .await?
+            .into_found(&url)
+    }
+
+    /// Follows any `next` links provided by the API, appending each page until there are none left
+    async fn fetch_paginated_data<T>(&self, url: Url, timeout: Option<Duration>) -> Result<T>
+    where
+        T: DeserializeOwned + Paginated,
+    {
+        let ApiResponse {
+            mut data, mut next, ..
+        } = self.fetch_found_api_response::<T>(url, timeout).await?;
+        let mut pages = 1;
+        while let Some(next_page) = next {
+            if pages == MAX_PAGES {
+                return Err(CompendiumError::PageLimitExceeded(MAX_PAGES));
+            }
+            let page = self
+                .fetch_found_api_response::<T>(
+                    self.create_path(&self.base_url, next_page)?,
+                    timeout,
+                )
+                .await?;
+            data.append_page(page.data);
+            next = page.next;
+            pages += 1;
+        }
+        Ok(data)
+    }
+
+    async fn fetch_data_for_specific_category(
+        &self,
+        url: Url,
+        entry_type: CompendiumCategory,
+    ) -> Result<CategoryResult> {
+        let category_result = match entry_type {
+            #[cfg(feature = "monsters")]
+            CompendiumCategory::Monster => CategoryResult::Monsters(
+                self.fetch_paginated_data::<OneOrMany<_>>(url, None)
+                    .await?
+                    .into_vec(),
+            ),
+            #[cfg(feature = "materials")]
+            CompendiumCategory::Material => CategoryResult::Materials(
+                self.fetch_paginated_data::<OneOrMany<_>>(url, None)
+                    .await?
+                    .into_vec(),
+            ),
+            #[cfg(feature = "treasure")]
+            CompendiumCategory::Treasure => CategoryResult::Treasure(
+                self.fetch_paginated_data::<OneOrMany<_>>(url, None)
+                    .await?
+                    .into_vec(),
+            ),
+            #[cfg(feature = "creatures")]
+            CompendiumCategory::Creature => {
+                CategoryResult::Creatures(self.fetch_paginated_data(url, None).await?)
+            }
+            #[cfg(feature = "equipment")]
+            CompendiumCategory::Equipment => CategoryResult::Equipment(
+                self.fetch_paginated_data::<OneOrMany<_>>(url, None)
+                    .await?
+                    .into_vec(),
+            ),
+        };
+        if self.config.validate_category_types {
+            validate_category_types(category_path_for_type(&entry_type), &category_result)?;
+        }
+        if self.config.detect_duplicate_ids {
+            validate_unique_ids(category_result.entries())?;
+        }
+        Ok(category_result)
+    }
+
+    async fn fetch_data_for_specified_entry<T>(
+        &self,
+        identifier: EntryIdentifier<'_>,
+        game_mode: GameMode,
+    ) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let url = self.create_path(&self.base_url, entry_path(&identifier, game_mode))?;
+        let requested_url = url.clone();
+        self.fetch_api_response::<serde_json::Value>(url, None)
+            .await
+            .and_then(|api_response| api_response.into_data(&requested_url))
+    }
+}
+
+fn default_network_client() -> Client {
+    Client::builder()
+        .user_agent(DEFAULT_USER_AGENT)
+        .build()
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::CompendiumEntry;
+    use mockito::{mock, server_url};
+    use tokio::io::AsyncReadExt;
+    use tokio::net::TcpListener;
+
+    fn silver_moblin_data<'a>() -> &'a str {
+        r#"{"data":{"category":"monsters","common_locations":null,"description":"The strongest of all Moblins, Ganon's fiendish magic has allowed them to surpass even the Black Moblins in strength and resilience. They're called \"silver\" for both their body color as well as their rarity. The purple patterns on their bodies also help them to stand out.","drops":["moblin horn","moblin fang","moblin guts","amber","opal","topaz","ruby","sapphire","diamond"],"id":112,"image":"https://botw-compendium.herokuapp.com/api/v2/entry/silver_moblin/image","name":"silver moblin"}}"#
+    }
+
+    #[tokio::test]
+    async fn test_async_compendium_client_monster_entry_search() {
+        let mock = mock("GET", "/entry/silver_moblin")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(silver_moblin_data())
+            .create();
+        let compendium = CompendiumClient::new(server_url().as_str()).unwrap();
+        let result = compendium
+            .entry(EntryIdentifier::Name("silver moblin"))
+            .await
+            .unwrap();
+        assert_eq!(112, result.id());
+        mock.assert()
+    }
+
+    #[tokio::test]
+    async fn test_async_compendium_client_missing_monster_response() {
+        let mock = mock("GET", "/entry/example_monster")
+            .with_status(404)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"data":{},"message":"no results"}"#)
+            .create();
+        let compendium = CompendiumClient::new(server_url().as_str()).unwrap();
+        let result = compendium
+            .monster(EntryIdentifier::Name("example_monster"))
+            .await;
+        assert!(matches!(result, Err(CompendiumError::NoDataFound(_))));
+        mock.assert()
+    }
+
+    #[tokio::test]
+    async fn test_async_compendium_client_category_without_results() {
+        let mock = mock("GET", "/category/monsters")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"data":{},"message":"no results"}"#)
+            .create();
+        let compendium = CompendiumClient::new(server_url().as_str()).unwrap();
+        let result = compendium.category(CompendiumCategory::Monster).await;
+        assert!(matches!(
+            result,
+            Err(CompendiumError::NoDataFound(path)) if path == "/category/monsters"
+        ));
+        mock.assert()
+    }
+
+    #[tokio::test]
+    async fn test_async_compendium_client_entry_with_fallback() {
+        let missing_mock = mock("GET", "/entry/missing_moblin")
+            .with_status(404)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"data":{},"message":"no results"}"#)
+            .create();
+        let mock = mock("GET", "/entry/silver_moblin")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(silver_moblin_data())
+            .create();
+        let compendium = CompendiumClient::new(server_url().as_str()).unwrap();
+        let summary = compendium
+            .entry_with_fallback(
+                EntryIdentifier::Name("missing moblin"),
+                EntryIdentifier::Name("silver moblin"),
+            )
+            .await
+            .map(|entry| EntrySummary::from(&entry))
+            .unwrap();
+        assert_eq!(112, summary.id());
+        assert_eq!("monsters", summary.category());
+        missing_mock.assert();
+        mock.assert()
+    }
+
+    #[tokio::test]
+    async fn test_async_compendium_client_dropped_request_is_cancelled() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}/", listener.local_addr().unwrap());
+        let compendium = CompendiumClient::new(&base_url).unwrap();
+
+        let request = compendium.entry(EntryIdentifier::Id(112));
+        let (server_result, request_result) = tokio::join!(
+            async {
+                let (mut connection, _) = listener.accept().await.unwrap();
+                let mut buffer = [0; 1024];
+                // Read the request but never respond, leaving it in flight until it's dropped
+                let read = connection.read(&mut buffer).await.unwrap();
+                assert!(read > 0);
+                connection.read(&mut buffer).await
+            },
+            tokio::time::timeout(Duration::from_millis(100), request)
+        );
+
+        assert!(request_result.is_err());
+        // The dropped request closes its connection, ending the stream
+        assert_eq!(0, server_result.unwrap());
+    }
+}
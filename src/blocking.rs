@@ -1,6 +1,21 @@
 //! Blocking module
 
+/// The builder for the blocking compendium client
+mod builder;
+/// The blocking compendium client that caches the entries it requests
+#[cfg(feature = "cache")]
+mod caching;
 /// The blocking compendium client
 mod compendium;
+/// The blocking compendium client that fails over to mirrors of the API
+mod failover;
+/// Client-side rate limiting of the requests made to the API
+mod rate_limit;
+/// Deserializing all entries one at a time as they're read from the response
+mod streaming;
 
+pub use builder::CompendiumClientBuilder;
+#[cfg(feature = "cache")]
+pub use caching::CachingCompendiumClient;
 pub use compendium::*;
+pub use failover::FailoverCompendiumClient;
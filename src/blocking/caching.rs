@@ -0,0 +1,277 @@
+use super::compendium::{CompendiumApiClient, CompendiumClient, CompendiumSealed};
+use crate::api::entry_path;
+use crate::domain::inputs::{CompendiumCategory, EntryIdentifier, GameMode};
+#[cfg(feature = "creatures")]
+use crate::domain::models::CreatureEntry;
+#[cfg(feature = "equipment")]
+use crate::domain::models::EquipmentEntry;
+#[cfg(feature = "materials")]
+use crate::domain::models::MaterialEntry;
+#[cfg(feature = "monsters")]
+use crate::domain::models::MonsterEntry;
+#[cfg(feature = "treasure")]
+use crate::domain::models::TreasureEntry;
+use crate::domain::responses::{AllStandardEntries, CategoryResult, EntryResponse};
+use crate::result::Result;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, MutexGuard};
+
+/// A compendium client that keeps the entries it has requested in memory, serving repeated requests for an entry without requesting it again.
+///
+/// Entries are cached by the normalized path of their identifier (e.g. `entry/silver_moblin` or `entry/112`), so names differing
+/// only by case share a cached entry. An entry requested by both id and name is cached independently under each identifier.
+/// Only `entry()`, `monster()` and `master_mode_monster()` are cached, every other request is made as normal.
+/// Once the cache holds its maximum number of entries, the oldest cached entry is evicted to make room for the next.
+#[derive(Debug)]
+pub struct CachingCompendiumClient {
+    client: CompendiumClient,
+    entries: Mutex<BoundedCache<EntryResponse>>,
+    #[cfg(feature = "monsters")]
+    monsters: Mutex<BoundedCache<MonsterEntry>>,
+}
+
+impl CachingCompendiumClient {
+    /// Wrap a client, caching up to `max_size` entries (and separately up to `max_size` monsters)
+    pub fn new(client: CompendiumClient, max_size: usize) -> Self {
+        CachingCompendiumClient {
+            client,
+            entries: Mutex::new(BoundedCache::new(max_size)),
+            #[cfg(feature = "monsters")]
+            monsters: Mutex::new(BoundedCache::new(max_size)),
+        }
+    }
+
+    /// Remove every cached entry, so the next request for each entry is made to the API
+    pub fn clear(&self) {
+        lock(&self.entries).clear();
+        #[cfg(feature = "monsters")]
+        lock(&self.monsters).clear();
+    }
+
+    /// The number of entries currently cached
+    pub fn len(&self) -> usize {
+        let len = lock(&self.entries).len();
+        #[cfg(feature = "monsters")]
+        let len = len + lock(&self.monsters).len();
+        len
+    }
+
+    /// Whether there are no entries cached
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn cached<T: Clone>(
+        cache: &Mutex<BoundedCache<T>>,
+        key: String,
+        request: impl FnOnce() -> Result<T>,
+    ) -> Result<T> {
+        if let Some(value) = lock(cache).get(&key) {
+            return Ok(value);
+        }
+        let value = request()?;
+        lock(cache).put(key, value.clone());
+        Ok(value)
+    }
+}
+
+impl CompendiumApiClient for CachingCompendiumClient {
+    fn entry(&self, identifier: EntryIdentifier) -> Result<EntryResponse> {
+        Self::cached(
+            &self.entries,
+            cache_key(&identifier, GameMode::Standard),
+            || self.client.entry(identifier),
+        )
+    }
+
+    #[cfg(feature = "monsters")]
+    fn monster(&self, identifier: EntryIdentifier) -> Result<MonsterEntry> {
+        Self::cached(
+            &self.monsters,
+            cache_key(&identifier, GameMode::Standard),
+            || self.client.monster(identifier),
+        )
+    }
+
+    #[cfg(feature = "monsters")]
+    fn master_mode_monster(&self, identifier: EntryIdentifier) -> Result<MonsterEntry> {
+        Self::cached(
+            &self.monsters,
+            cache_key(&identifier, GameMode::MasterMode),
+            || self.client.master_mode_monster(identifier),
+        )
+    }
+
+    #[cfg(feature = "treasure")]
+    fn treasure(&self, identifier: EntryIdentifier) -> Result<TreasureEntry> {
+        self.client.treasure(identifier)
+    }
+
+    #[cfg(feature = "creatures")]
+    fn creature(&self, identifier: EntryIdentifier) -> Result<CreatureEntry> {
+        self.client.creature(identifier)
+    }
+
+    #[cfg(feature = "materials")]
+    fn material(&self, identifier: EntryIdentifier) -> Result<MaterialEntry> {
+        self.client.material(identifier)
+    }
+
+    #[cfg(feature = "equipment")]
+    fn equipment(&self, identifier: EntryIdentifier) -> Result<EquipmentEntry> {
+        self.client.equipment(identifier)
+    }
+
+    fn category(&self, category: CompendiumCategory) -> Result<CategoryResult> {
+        self.client.category(category)
+    }
+
+    fn master_mode_category(&self, category: CompendiumCategory) -> Result<CategoryResult> {
+        self.client.master_mode_category(category)
+    }
+
+    fn all_entries(&self) -> Result<AllStandardEntries> {
+        self.client.all_entries()
+    }
+
+    #[cfg(feature = "monsters")]
+    fn all_master_mode_entries(&self) -> Result<Vec<MonsterEntry>> {
+        self.client.all_master_mode_entries()
+    }
+}
+
+impl CompendiumSealed for CachingCompendiumClient {}
+
+fn cache_key(identifier: &EntryIdentifier, mode: GameMode) -> String {
+    entry_path(identifier, mode)
+}
+
+fn lock<T>(cache: &Mutex<T>) -> MutexGuard<'_, T> {
+    cache
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Values keyed by string, evicting the oldest value once the maximum size is reached
+#[derive(Debug)]
+struct BoundedCache<V> {
+    values: HashMap<String, V>,
+    insertion_order: VecDeque<String>,
+    max_size: usize,
+}
+
+impl<V: Clone> BoundedCache<V> {
+    fn new(max_size: usize) -> Self {
+        BoundedCache {
+            values: HashMap::new(),
+            insertion_order: VecDeque::new(),
+            max_size,
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<V> {
+        self.values.get(key).cloned()
+    }
+
+    fn put(&mut self, key: String, value: V) {
+        if self.max_size == 0 {
+            return;
+        }
+        if self.values.insert(key.clone(), value).is_some() {
+            return;
+        }
+        self.insertion_order.push_back(key);
+        if self.insertion_order.len() > self.max_size {
+            if let Some(oldest) = self.insertion_order.pop_front() {
+                self.values.remove(&oldest);
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.values.clear();
+        self.insertion_order.clear();
+    }
+
+    fn len(&self) -> usize {
+        self.values.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::CompendiumEntry;
+    use mockito::{mock, server_url, Mock};
+
+    fn silver_moblin_data<'a>() -> &'a str {
+        r#"{"data":{"category":"monsters","common_locations":null,"description":"The strongest of all Moblins.","drops":["moblin horn"],"id":112,"image":"https://botw-compendium.herokuapp.com/api/v2/entry/silver_moblin/image","name":"silver moblin"}}"#
+    }
+
+    fn create_mock(path: &str, hits: usize) -> Mock {
+        mock("GET", path)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(silver_moblin_data())
+            .expect(hits)
+            .create()
+    }
+
+    fn create_client(max_size: usize) -> CachingCompendiumClient {
+        let base_url = format!("{}/caching/", server_url());
+        CachingCompendiumClient::new(CompendiumClient::new(&base_url).unwrap(), max_size)
+    }
+
+    #[test]
+    fn test_caching_client_serves_repeated_entries_from_cache() {
+        let mock = create_mock("/caching/entry/silver_moblin", 1);
+        let client = create_client(10);
+        let first = client
+            .entry(EntryIdentifier::Name("silver moblin"))
+            .unwrap();
+        let second = client
+            .entry(EntryIdentifier::Name("Silver Moblin"))
+            .unwrap();
+        assert_eq!(first.id(), second.id());
+        assert_eq!(1, client.len());
+        mock.assert()
+    }
+
+    #[test]
+    fn test_caching_client_caches_monsters() {
+        let mock = create_mock("/caching/entry/112", 1);
+        let client = create_client(10);
+        for _ in 0..2 {
+            assert_eq!(112, client.monster(EntryIdentifier::Id(112)).unwrap().id());
+        }
+        mock.assert()
+    }
+
+    #[test]
+    fn test_caching_client_clear() {
+        let mock = create_mock("/caching/master_mode/entry/112", 2);
+        let client = create_client(10);
+        client
+            .master_mode_monster(EntryIdentifier::Id(112))
+            .unwrap();
+        client.clear();
+        assert!(client.is_empty());
+        client
+            .master_mode_monster(EntryIdentifier::Id(112))
+            .unwrap();
+        mock.assert()
+    }
+
+    #[test]
+    fn test_bounded_cache_evicts_oldest_value() {
+        let mut cache = BoundedCache::new(2);
+        cache.put("first".to_string(), 1);
+        cache.put("second".to_string(), 2);
+        cache.put("first".to_string(), 10);
+        cache.put("third".to_string(), 3);
+        assert_eq!(None, cache.get("first"));
+        assert_eq!(Some(2), cache.get("second"));
+        assert_eq!(Some(3), cache.get("third"));
+        assert_eq!(2, cache.len());
+    }
+}
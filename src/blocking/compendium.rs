@@ -1,20 +1,54 @@
-use crate::domain::inputs::{CompendiumCategory, EntryIdentifier, GameMode};
-use crate::domain::models::{
-    CreatureEntry, EquipmentEntry, MaterialEntry, MonsterEntry, TreasureEntry,
+use super::builder::{CompendiumClientBuilder, RequestInterceptor};
+use super::rate_limit::RateLimiter;
+use super::streaming::{EntrySink, PageSeed};
+#[cfg(any(
+    feature = "equipment",
+    feature = "materials",
+    feature = "monsters",
+    feature = "treasure"
+))]
+use crate::api::OneOrMany;
+use crate::api::{
+    category_discrepancies, category_path, category_path_for_type, check_status, entry_path,
+    rank_entry, validate_all_entries_unique_ids, validate_category_types, validate_unique_ids,
+    ApiResponse, MaybeEmpty, Paginated, MAX_PAGES,
+};
+use crate::cache::CacheBackend;
+use crate::config::{ClientConfig, DEFAULT_BASE_URL, DEFAULT_USER_AGENT, MAX_BACKOFF};
+use crate::domain::inputs::{
+    CompendiumCategory, EntryIdentifier, FailurePolicy, GameMode, GameVersion,
+};
+#[cfg(feature = "creatures")]
+use crate::domain::models::CreatureEntry;
+#[cfg(feature = "equipment")]
+use crate::domain::models::EquipmentEntry;
+#[cfg(feature = "materials")]
+use crate::domain::models::MaterialEntry;
+#[cfg(feature = "monsters")]
+use crate::domain::models::MonsterEntry;
+#[cfg(feature = "treasure")]
+use crate::domain::models::TreasureEntry;
+use crate::domain::models::{CompendiumEntry, EntrySummary};
+use crate::domain::responses::{
+    AllStandardEntries, CategoryCounts, CategoryResult, DatasetDrift, EntryResponse,
 };
-use crate::domain::responses::{AllStandardEntries, CategoryResult, EntryResponse};
 use crate::error::CompendiumError;
 use crate::result::Result;
 use reqwest::{
     blocking::{Client, Response},
     Url,
 };
-use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde::de::{DeserializeOwned, DeserializeSeed};
+use std::fs;
+use std::io::BufReader;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
-struct ApiResponse<T> {
-    data: T,
-}
+/// The maximum number of requests made at once by [entries](CompendiumClient::entries)
+const MAX_CONCURRENT_REQUESTS: usize = 4;
 
 /// Sealing the trait not to be used by other consumers
 pub trait CompendiumSealed {}
@@ -45,12 +79,29 @@ pub trait CompendiumApiClient: CompendiumSealed {
     /// }
     /// ```
     fn entry(&self, identifier: EntryIdentifier) -> Result<EntryResponse>;
+    /// Get an entry by a primary [identifier](crate::domain::inputs::EntryIdentifier), falling back to a second identifier when no data is found for the first.
+    /// Only a [NoDataFound](crate::CompendiumError::NoDataFound) error triggers the fallback, any other error is returned straight away.
+    fn entry_with_fallback(
+        &self,
+        primary: EntryIdentifier,
+        fallback: EntryIdentifier,
+    ) -> Result<EntryResponse> {
+        match self.entry(primary) {
+            Err(CompendiumError::NoDataFound(_)) => self.entry(fallback),
+            result => result,
+        }
+    }
+    /// Get a lightweight [summary](crate::domain::models::EntrySummary) of an entry by [identifier](crate::domain::inputs::EntryIdentifier)
+    fn entry_summary(&self, identifier: EntryIdentifier) -> Result<EntrySummary> {
+        self.entry(identifier)
+            .map(|entry| EntrySummary::from(&entry))
+    }
     /// Get a [monster entry](crate::domain::models::MonsterEntry) by [identifier](crate::domain::inputs::EntryIdentifier)
     /// ```rust
     /// use rusty_hyrule_compendium::blocking::{CompendiumApiClient, CompendiumClient};
     /// use rusty_hyrule_compendium::domain::inputs::EntryIdentifier;
     /// use rusty_hyrule_compendium::Result;
-
+    ///
     /// fn main() -> Result<()> {
     ///     // Preconfigured client using v2 of the API
     ///     let client = CompendiumClient::default();
@@ -63,16 +114,22 @@ pub trait CompendiumApiClient: CompendiumSealed {
     ///     Ok(())
     /// }
     /// ```
+    #[cfg(feature = "monsters")]
     fn monster(&self, identifier: EntryIdentifier) -> Result<MonsterEntry>;
     /// Get specifically a [monster entry](crate::domain::models::MonsterEntry) that exists only in master mode by [identifier](crate::domain::inputs::EntryIdentifier)
+    #[cfg(feature = "monsters")]
     fn master_mode_monster(&self, identifier: EntryIdentifier) -> Result<MonsterEntry>;
     /// Get specifically a [treasure entry](crate::domain::models::TreasureEntry) by [identifier](crate::domain::inputs::EntryIdentifier)
+    #[cfg(feature = "treasure")]
     fn treasure(&self, identifier: EntryIdentifier) -> Result<TreasureEntry>;
     /// Get specifically a [creature entry](crate::domain::models::CreatureEntry) by [identifier](crate::domain::inputs::EntryIdentifier)
+    #[cfg(feature = "creatures")]
     fn creature(&self, identifier: EntryIdentifier) -> Result<CreatureEntry>;
     /// Get specifically a [material entry](crate::domain::models::MaterialEntry) by [identifier](crate::domain::inputs::EntryIdentifier)
+    #[cfg(feature = "materials")]
     fn material(&self, identifier: EntryIdentifier) -> Result<MaterialEntry>;
     /// Get specifically an [equipment entry](crate::domain::models::EquipmentEntry) by [identifier](crate::domain::inputs::EntryIdentifier)
+    #[cfg(feature = "equipment")]
     fn equipment(&self, identifier: EntryIdentifier) -> Result<EquipmentEntry>;
     /// Get all entries for a given a category
     /// ```rust
@@ -80,7 +137,7 @@ pub trait CompendiumApiClient: CompendiumSealed {
     /// use rusty_hyrule_compendium::domain::inputs::CompendiumCategory;
     /// use rusty_hyrule_compendium::domain::responses::CategoryResult;
     /// use rusty_hyrule_compendium::Result;
-
+    ///
     /// fn main() -> Result<()> {
     ///     // Preconfigured client using v2 of the API
     ///     let client = CompendiumClient::default();
@@ -95,7 +152,11 @@ pub trait CompendiumApiClient: CompendiumSealed {
     /// }
     /// ```
     fn category(&self, category: CompendiumCategory) -> Result<CategoryResult>;
-    /// Get all entries in the compendium (excluding master mode)
+    /// Get all master mode entries for a given category.
+    /// Master mode only adds monsters, so the API's response for any other category is returned as is, which is typically no entries.
+    fn master_mode_category(&self, category: CompendiumCategory) -> Result<CategoryResult>;
+    /// Get all entries in the compendium (excluding master mode).
+    /// This uses the bulk timeout when one is configured, see [bulk_timeout](crate::blocking::CompendiumClientBuilder::bulk_timeout)
     /// ```rust
     /// use rusty_hyrule_compendium::blocking::{CompendiumApiClient, CompendiumClient};
     /// use rusty_hyrule_compendium::domain::inputs::CompendiumCategory;
@@ -112,34 +173,347 @@ pub trait CompendiumApiClient: CompendiumSealed {
     /// }
     /// ```
     fn all_entries(&self) -> Result<AllStandardEntries>;
-    /// Get all [master mode entries](crate::domain::models::MonsterEntry) (which are only monsters) in the compendium
+    /// Get all entries in the compendium by requesting each category in turn, rather than requesting all entries at once.
+    /// With [CollectErrors](crate::domain::inputs::FailurePolicy::CollectErrors) the categories that failed are left empty and their errors are returned alongside,
+    /// with [AbortOnFirst](crate::domain::inputs::FailurePolicy::AbortOnFirst) the first error is returned instead.
+    fn all_entries_via_categories(
+        &self,
+        policy: FailurePolicy,
+    ) -> Result<(AllStandardEntries, Vec<CompendiumError>)> {
+        let mut all_entries = AllStandardEntries::default();
+        let mut errors = Vec::new();
+        for category in CompendiumCategory::ALL {
+            match self.category(*category) {
+                Ok(category_result) => all_entries.replace_category(category_result),
+                Err(error) if policy == FailurePolicy::CollectErrors => errors.push(error),
+                Err(error) => return Err(error),
+            }
+        }
+        Ok((all_entries, errors))
+    }
+    /// Get the ids of entries that are returned for the category but missing from all entries, or vice versa, sorted by id.
+    /// This requests both the category and all entries so is useful for checking the API's consistency rather than routine use.
+    fn category_vs_all_discrepancies(&self, category: CompendiumCategory) -> Result<Vec<i32>> {
+        Ok(category_discrepancies(
+            &self.category(category)?,
+            &self.all_entries()?,
+            category,
+        ))
+    }
+    /// Get the number of entries in each category, e.g. for a dashboard.
+    /// The API has no way of counting entries, so this requests all entries and counts them: it costs as much as [all_entries](Self::all_entries),
+    /// but is still a single request rather than one per category.
+    fn category_counts(&self) -> Result<CategoryCounts> {
+        Ok(self.all_entries()?.category_counts())
+    }
+    /// Find the entries whose name or description contains the query, ignoring case, with name matches first.
+    /// This downloads every entry in the compendium for each search, so consider requesting [all_entries](Self::all_entries) once
+    /// and using [search](crate::domain::responses::AllStandardEntries::search) on them when searching repeatedly.
+    fn search(&self, query: &str) -> Result<Vec<EntryResponse>> {
+        Ok(self.all_entries()?.search(query))
+    }
+    /// Find the entries whose name is close to the query, best match first, for when a name may be mistyped.
+    /// This requests all entries and matches names as described in [find_by_name_fuzzy](crate::domain::responses::AllStandardEntries::find_by_name_fuzzy).
+    fn find_by_name_fuzzy(&self, query: &str, max_distance: usize) -> Result<Vec<EntryResponse>> {
+        Ok(self.all_entries()?.find_by_name_fuzzy(query, max_distance))
+    }
+    /// Get the 1-based position of an entry when every entry is sorted by id, along with the total number of entries, e.g. for showing "112 of 389".
+    /// This requests all entries so the ranking is over the standard dataset, master mode entries aren't included.
+    /// Names are matched ignoring case and a [NoDataFound](crate::CompendiumError::NoDataFound) error is returned when no entry matches.
+    fn entry_rank(&self, identifier: EntryIdentifier) -> Result<(usize, usize)> {
+        rank_entry(&self.all_entries()?, identifier)
+    }
+    /// Get all [master mode entries](crate::domain::models::MonsterEntry) (which are only monsters) in the compendium.
+    /// This uses the bulk timeout when one is configured, see [bulk_timeout](crate::blocking::CompendiumClientBuilder::bulk_timeout)
+    #[cfg(feature = "monsters")]
     fn all_master_mode_entries(&self) -> Result<Vec<MonsterEntry>>;
 }
 
 /// The CompendiumClient that can be used to obtain relevant entries
 #[derive(Debug, Clone)]
 pub struct CompendiumClient {
-    base_url: Url,
-    network_client: Client,
+    pub(super) base_url: Url,
+    pub(super) network_client: Client,
+    pub(super) rate_limiter: Option<Arc<RateLimiter>>,
+    pub(super) config: ClientConfig,
+    pub(super) request_interceptor: Option<RequestInterceptor>,
+    pub(super) cache: Option<Arc<dyn CacheBackend>>,
 }
 
 impl Default for CompendiumClient {
     fn default() -> CompendiumClient {
         CompendiumClient {
-            base_url: Url::parse("https://botw-compendium.herokuapp.com/api/v2/").unwrap(),
-            network_client: Client::new(),
+            base_url: Url::parse(DEFAULT_BASE_URL).unwrap(),
+            network_client: Client::builder()
+                .user_agent(DEFAULT_USER_AGENT)
+                .build()
+                .unwrap(),
+            rate_limiter: None,
+            config: ClientConfig::default(),
+            request_interceptor: None,
+            cache: None,
         }
     }
 }
 
 impl CompendiumClient {
-    /// A convience method to initialise a compendium client if the CompendiumClient::Default() isn't sufficient
+    /// A convience method to initialise a compendium client if the CompendiumClient::Default() isn't sufficient.
+    /// A trailing slash is appended to the url when it's missing, see [base_url](crate::blocking::CompendiumClientBuilder::base_url)
     pub fn new(url: &str) -> Result<Self> {
-        Ok(CompendiumClient {
-            base_url: Url::parse(url)
-                .map_err(|_e| CompendiumError::InvalidBaseUrl(url.to_string()))?,
-            network_client: Client::new(),
-        })
+        CompendiumClientBuilder::new().base_url(url).build()
+    }
+
+    /// A convience method to initialise a compendium client that identifies itself with the given User-Agent header,
+    /// see [user_agent](crate::blocking::CompendiumClientBuilder::user_agent)
+    pub fn with_user_agent(url: &str, user_agent: &str) -> Result<Self> {
+        CompendiumClientBuilder::new()
+            .base_url(url)
+            .user_agent(user_agent)
+            .build()
+    }
+
+    /// A convience method to initialise a compendium client that makes its requests with an already configured HTTP client,
+    /// e.g. one shared with the rest of an application for its connection pool, proxy and TLS settings.
+    /// The base url is validated as with [new](Self::new). Timeouts, the User-Agent header and compression then come from the given client,
+    /// see [http_client](crate::blocking::CompendiumClientBuilder::http_client).
+    pub fn with_client(url: &str, client: Client) -> Result<Self> {
+        CompendiumClientBuilder::new()
+            .base_url(url)
+            .http_client(client)
+            .build()
+    }
+
+    /// A convience method to initialise a compendium client for the given game, see [game_version](crate::blocking::CompendiumClientBuilder::game_version)
+    pub fn for_version(game_version: GameVersion) -> Result<Self> {
+        CompendiumClientBuilder::new()
+            .game_version(game_version)
+            .build()
+    }
+
+    /// A builder to configure the client beyond the base url, see [CompendiumClientBuilder](crate::blocking::CompendiumClientBuilder)
+    pub fn builder() -> CompendiumClientBuilder {
+        CompendiumClientBuilder::new()
+    }
+
+    /// The base url of the API the client requests data from
+    pub fn base_url(&self) -> &Url {
+        &self.base_url
+    }
+
+    /// The configuration the client was built with
+    pub fn config(&self) -> &ClientConfig {
+        &self.config
+    }
+
+    /// Request any path of the API, relative to the base url, for endpoints the client doesn't cover yet.
+    /// The response must still be wrapped in the API's `{"data": ...}` envelope, it's the data inside that's deserialized into `T`.
+    /// ```rust,no_run
+    /// use rusty_hyrule_compendium::blocking::CompendiumClient;
+    /// use rusty_hyrule_compendium::domain::models::MonsterEntry;
+    /// use rusty_hyrule_compendium::Result;
+    ///
+    /// fn main() -> Result<()> {
+    ///     let client = CompendiumClient::default();
+    ///     let monsters: Vec<MonsterEntry> = client.fetch_path("category/monsters")?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn fetch_path<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        let url = self.create_path(&self.base_url, path)?;
+        self.fetch_data_for_specified_type(url)
+    }
+
+    /// Get all entries in the compendium (excluding master mode), passing each to the callback as it's read from the response.
+    ///
+    /// [all_entries](CompendiumApiClient::all_entries) builds every category in memory before returning, which is heavy on constrained devices.
+    /// This only holds one entry at a time (plus the ids seen so far when [detect_duplicate_ids](crate::blocking::CompendiumClientBuilder::detect_duplicate_ids) is on),
+    /// so peak memory stays small however many entries there are. The tradeoff is that the entries can't be revisited without requesting them again,
+    /// and if the response turns out to be invalid part way through, the callback has already been called for the entries before the error.
+    /// Entries are passed in the order the API returns them and any further pages are followed, as with `all_entries`.
+    /// ```rust,no_run
+    /// use rusty_hyrule_compendium::blocking::CompendiumClient;
+    /// use rusty_hyrule_compendium::domain::models::CompendiumEntry;
+    /// use rusty_hyrule_compendium::Result;
+    ///
+    /// fn main() -> Result<()> {
+    ///     let client = CompendiumClient::default();
+    ///     let mut longest_name = String::new();
+    ///     client.all_entries_streamed(|entry| {
+    ///         if entry.name().len() > longest_name.len() {
+    ///             longest_name = entry.name().to_string();
+    ///         }
+    ///     })?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn all_entries_streamed<F: FnMut(EntryResponse)>(&self, f: F) -> Result<()> {
+        let mut sink = EntrySink::new(f, self.config.detect_duplicate_ids);
+        let mut url = self.create_path(&self.base_url, "all")?;
+        let mut pages = 0;
+        loop {
+            let path = url.path().to_string();
+            let response = self.make_request(url, self.config.bulk_timeout)?;
+            let mut deserializer = serde_json::Deserializer::from_reader(BufReader::new(response));
+            let page = PageSeed(&mut sink)
+                .deserialize(&mut deserializer)
+                .and_then(|page| deserializer.end().map(|()| page))
+                .map_err(|error| match sink.duplicate_id() {
+                    Some(id) => CompendiumError::DuplicateId(id),
+                    None => CompendiumError::from_streamed_body_error(error),
+                })?;
+            if page.no_results {
+                return Err(CompendiumError::NoDataFound(path));
+            }
+            pages += 1;
+            match page.next {
+                None => return Ok(()),
+                Some(_) if pages == MAX_PAGES => {
+                    return Err(CompendiumError::PageLimitExceeded(MAX_PAGES))
+                }
+                Some(next_page) => url = self.create_path(&self.base_url, next_page)?,
+            }
+        }
+    }
+
+    /// Compare all entries from this client's API with those from another client's, e.g. to check a mirror is in sync.
+    /// The result reports, per category, the entries the other API has added, removed or changed relative to this one, see [drift](crate::domain::responses::AllStandardEntries::drift).
+    pub fn dataset_drift(&self, other: &CompendiumClient) -> Result<DatasetDrift> {
+        let all_entries = self.all_entries()?;
+        let other_entries = other.all_entries()?;
+        Ok(all_entries.drift(&other_entries))
+    }
+
+    /// Get an entry, allowing for the API's host to be asleep.
+    /// The host sleeps while idle, so the first request afterwards can fail to connect or time out while it wakes up.
+    /// When that happens this waits for the [wake up delay](crate::ClientConfig::wake_up_delay) and tries once more before giving up.
+    pub fn wake_and_fetch(&self, identifier: EntryIdentifier) -> Result<EntryResponse> {
+        match self.entry(identifier.clone()) {
+            Err(CompendiumError::Timeout(_) | CompendiumError::ConnectionError(_)) => {
+                thread::sleep(self.config.wake_up_delay);
+                self.entry(identifier)
+            }
+            result => result,
+        }
+    }
+
+    /// Request each of the given entries concurrently, making at most 4 requests at once over the client's shared connection pool.
+    /// The results are returned in the order of the identifiers, each independently carrying its entry or error,
+    /// so a missing entry doesn't prevent the rest from being returned.
+    pub fn entries(&self, ids: &[EntryIdentifier]) -> Vec<Result<EntryResponse>> {
+        let next_index = AtomicUsize::new(0);
+        let mut results: Vec<(usize, Result<EntryResponse>)> = thread::scope(|scope| {
+            let workers: Vec<_> = (0..MAX_CONCURRENT_REQUESTS.min(ids.len()))
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut results = Vec::new();
+                        loop {
+                            let index = next_index.fetch_add(1, Ordering::Relaxed);
+                            match ids.get(index) {
+                                Some(identifier) => {
+                                    results.push((index, self.entry(identifier.clone())))
+                                }
+                                None => return results,
+                            }
+                        }
+                    })
+                })
+                .collect();
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().expect("requesting an entry doesn't panic"))
+                .collect()
+        });
+        results.sort_by_key(|(index, _)| *index);
+        results.into_iter().map(|(_, result)| result).collect()
+    }
+
+    /// Request each of the given entries in order, filling the [cache](crate::blocking::CompendiumClientBuilder::cache) when one is configured.
+    /// After each request `on_progress` is called with the number of entries requested so far and the total, e.g. to update a progress bar.
+    /// The results are returned in the order of the identifiers. With [CollectErrors](crate::domain::inputs::FailurePolicy::CollectErrors)
+    /// a failed request doesn't stop the remaining entries from being requested, with [AbortOnFirst](crate::domain::inputs::FailurePolicy::AbortOnFirst)
+    /// no more entries are requested after the first failure, which is the last result.
+    pub fn warm_cache<F: FnMut(usize, usize)>(
+        &self,
+        ids: &[EntryIdentifier],
+        policy: FailurePolicy,
+        mut on_progress: F,
+    ) -> Vec<Result<EntryResponse>> {
+        let total = ids.len();
+        let mut results = Vec::with_capacity(total);
+        for (index, identifier) in ids.iter().enumerate() {
+            let result = self.entry(identifier.clone());
+            let failed = result.is_err();
+            results.push(result);
+            on_progress(index + 1, total);
+            if failed && policy == FailurePolicy::AbortOnFirst {
+                break;
+            }
+        }
+        results
+    }
+
+    /// Get an entry by reading its page from the [web compendium](crate::blocking::CompendiumClientBuilder::web_url), as a best-effort fallback for when the API is unavailable.
+    /// The page's structure isn't part of the API, so an [UnexpectedHtml](crate::CompendiumError::UnexpectedHtml) error is returned if it changes.
+    #[cfg(feature = "scraping")]
+    pub fn entry_from_html(&self, identifier: EntryIdentifier) -> Result<EntryResponse> {
+        let web_url = Url::parse(&self.config.web_url)
+            .map_err(|_e| CompendiumError::InvalidBaseUrl(self.config.web_url.clone()))?;
+        let page_url = self.create_path(&web_url, entry_path(&identifier, GameMode::Standard))?;
+        let html = self
+            .make_request(page_url, None)?
+            .text()
+            .map_err(CompendiumError::ResponseParsingError)?;
+        crate::scraping::parse_entry_html(&html)
+    }
+
+    /// Download an entry's image as its raw bytes, see [download_image](Self::download_image)
+    pub fn download_entry_image<E: CompendiumEntry + ?Sized>(&self, entry: &E) -> Result<Vec<u8>> {
+        self.download_image(entry.image())
+    }
+
+    /// Download an image (see [download_image](Self::download_image)) and save it to the given path, replacing any existing file.
+    /// The image is written to a temporary file alongside the path first and then renamed, so the path never holds a partially written image.
+    /// The bytes are saved as they're downloaded, so it's up to the caller to give the path a suitable file extension (e.g. `.png`).
+    pub fn save_image<P: AsRef<Path>>(&self, image_url: &str, path: P) -> Result<()> {
+        let bytes = self.download_image(image_url)?;
+        let path = path.as_ref();
+        let file_name = path.file_name().ok_or_else(|| {
+            CompendiumError::IoError(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "the path must include a file name",
+            ))
+        })?;
+        let mut temporary_file_name = file_name.to_os_string();
+        temporary_file_name.push(".part");
+        let temporary_path = path.with_file_name(temporary_file_name);
+        fs::write(&temporary_path, bytes)
+            .and_then(|_| fs::rename(&temporary_path, path))
+            .map_err(|error| {
+                let _ = fs::remove_file(&temporary_path);
+                CompendiumError::IoError(error)
+            })
+    }
+
+    /// Get the width and height of an entry's image (e.g. from [image](crate::domain::models::CompendiumEntry::image)).
+    /// Only the image's header is decoded, the pixel data is never read.
+    #[cfg(feature = "image")]
+    pub fn image_dimensions(&self, url: &str) -> Result<(u32, u32)> {
+        let bytes = self.download_image(url)?;
+        image::ImageReader::new(std::io::Cursor::new(bytes))
+            .with_guessed_format()
+            .map_err(|error| CompendiumError::ImageDecodeError(image::ImageError::IoError(error)))?
+            .into_dimensions()
+            .map_err(CompendiumError::ImageDecodeError)
+    }
+
+    /// Download an image (e.g. from [image](crate::domain::models::CompendiumEntry::image)) as its raw bytes.
+    /// A relative url is resolved against the base url, and the response's status is checked the same as any other request.
+    pub fn download_image(&self, image_url: &str) -> Result<Vec<u8>> {
+        let image_url = self.create_path(&self.base_url, image_url)?;
+        self.make_request(image_url, None)?
+            .bytes()
+            .map(|bytes| bytes.to_vec())
+            .map_err(CompendiumError::ResponseParsingError)
     }
 
     fn create_path<S: Into<String>>(&self, url: &Url, path_to_add: S) -> Result<Url> {
@@ -148,46 +522,127 @@ impl CompendiumClient {
     }
 
     fn create_path_for_entry(&self, identifier: EntryIdentifier, mode: GameMode) -> Result<Url> {
-        let entry_identifier = match identifier {
-            EntryIdentifier::Id(id) => id.to_string(),
-            EntryIdentifier::Name(name) => name.replace(' ', "_"),
-        };
-        if mode == GameMode::MasterMode {
-            return self.create_path(
-                &self.base_url,
-                format!("master_mode/entry/{}", entry_identifier),
-            );
+        self.create_path(&self.base_url, entry_path(&identifier, mode))
+    }
+
+    fn make_request(&self, url: Url, timeout: Option<Duration>) -> Result<Response> {
+        self.with_retries(&url, || self.make_single_request(url.clone(), timeout))
+    }
+
+    /// Make a request to the url with `request`, retrying it while it fails with a transient error up to the configured number of retries
+    fn with_retries<T>(&self, url: &Url, mut request: impl FnMut() -> Result<T>) -> Result<T> {
+        #[cfg(feature = "tracing")]
+        let _span = crate::instrumentation::request_span(url).entered();
+        #[cfg(not(feature = "tracing"))]
+        let _ = url;
+        let mut attempt = 0;
+        loop {
+            match request() {
+                Err(error) if attempt < self.config.retries && is_transient(&error) => {
+                    thread::sleep(backoff_delay(self.config.base_backoff, attempt));
+                    attempt += 1;
+                }
+                result => return result,
+            }
         }
-        self.create_path(&self.base_url, format!("entry/{}", entry_identifier))
     }
 
-    fn make_request(&self, url: Url) -> Result<Response> {
-        self.network_client
-            .get(url)
+    fn make_single_request(&self, url: Url, timeout: Option<Duration>) -> Result<Response> {
+        #[cfg(feature = "tracing")]
+        crate::instrumentation::trace_request_sent(&url);
+        let request = self.network_client.get(url);
+        let request = match self.config.game_version.query_value() {
+            Some(game) => request.query(&[("game", game)]),
+            None => request,
+        };
+        let request = match timeout.or(self.config.timeout) {
+            Some(timeout) => request.timeout(timeout),
+            None => request,
+        };
+        let request = match &self.request_interceptor {
+            Some(interceptor) => interceptor.intercept(request),
+            None => request,
+        };
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire();
+        }
+        #[cfg(any(feature = "metrics", feature = "tracing"))]
+        let started = std::time::Instant::now();
+        let result = request
             .send()
-            .map_err(CompendiumError::RequestError)
-            .and_then(handle_response)
+            .map_err(CompendiumError::from_request_error)
+            .and_then(handle_response);
+        #[cfg(feature = "metrics")]
+        crate::instrumentation::record_request(started, &result);
+        #[cfg(feature = "tracing")]
+        crate::instrumentation::trace_response(started, &result, |response| {
+            response.status().as_u16()
+        });
+        result
     }
 
-    fn fetch_data_for_specified_type<T>(&self, url: Url) -> Result<T>
+    fn fetch_api_response<T>(&self, url: Url, timeout: Option<Duration>) -> Result<ApiResponse<T>>
     where
         T: DeserializeOwned,
     {
-        let response = self.make_request(url)?;
+        let response = self.make_request(url, timeout)?;
         response
             .json::<ApiResponse<T>>()
-            .map(|api_response| api_response.data)
             .map_err(CompendiumError::ResponseParsingError)
     }
 
-    fn category_path_for_type(&self, category: &CompendiumCategory) -> &str {
-        match category {
-            CompendiumCategory::Creature => "creatures",
-            CompendiumCategory::Monster => "monsters",
-            CompendiumCategory::Material => "materials",
-            CompendiumCategory::Treasure => "treasure",
-            CompendiumCategory::Equipment => "equipment",
+    fn fetch_data_for_specified_type<T>(&self, url: Url) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let requested_url = url.clone();
+        self.fetch_api_response::<serde_json::Value>(url, None)
+            .and_then(|api_response| api_response.into_data(&requested_url))
+    }
+
+    /// Deserializes the response while it's read from the connection, rather than buffering the whole body first.
+    /// This avoids holding both the raw JSON and the deserialized data in memory at once for large responses,
+    /// e.g. `cargo bench --bench all_entries_memory` measures the peak for an 8 MiB `all` response dropping from about 24 MiB to 16 MiB.
+    /// As the body is read after the response's headers arrive, the request is retried as a whole when reading the body times out or the connection fails.
+    /// A successful response without any results is a [NoDataFound](CompendiumError::NoDataFound) error, as with single entries.
+    fn fetch_streamed_api_response<T>(
+        &self,
+        url: Url,
+        timeout: Option<Duration>,
+    ) -> Result<ApiResponse<T>>
+    where
+        T: DeserializeOwned,
+    {
+        let api_response: ApiResponse<MaybeEmpty<T>> = self.with_retries(&url, || {
+            let response = self.make_single_request(url.clone(), timeout)?;
+            serde_json::from_reader(BufReader::new(response))
+                .map_err(CompendiumError::from_streamed_body_error)
+        })?;
+        api_response.into_found(&url)
+    }
+
+    /// Follows any `next` links provided by the API, appending each page until there are none left
+    fn fetch_paginated_data<T>(&self, url: Url, timeout: Option<Duration>) -> Result<T>
+    where
+        T: DeserializeOwned + Paginated,
+    {
+        let ApiResponse {
+            mut data, mut next, ..
+        } = self.fetch_streamed_api_response::<T>(url, timeout)?;
+        let mut pages = 1;
+        while let Some(next_page) = next {
+            if pages == MAX_PAGES {
+                return Err(CompendiumError::PageLimitExceeded(MAX_PAGES));
+            }
+            let page = self.fetch_streamed_api_response::<T>(
+                self.create_path(&self.base_url, next_page)?,
+                timeout,
+            )?;
+            data.append_page(page.data);
+            next = page.next;
+            pages += 1;
         }
+        Ok(data)
     }
 
     fn fetch_data_for_specific_category(
@@ -195,23 +650,39 @@ impl CompendiumClient {
         url: Url,
         entry_type: CompendiumCategory,
     ) -> Result<CategoryResult> {
-        match entry_type {
+        let category_result = match entry_type {
+            #[cfg(feature = "monsters")]
             CompendiumCategory::Monster => Ok(CategoryResult::Monsters(
-                self.fetch_data_for_specified_type(url)?,
+                self.fetch_paginated_data::<OneOrMany<_>>(url, None)?
+                    .into_vec(),
             )),
+            #[cfg(feature = "materials")]
             CompendiumCategory::Material => Ok(CategoryResult::Materials(
-                self.fetch_data_for_specified_type(url)?,
+                self.fetch_paginated_data::<OneOrMany<_>>(url, None)?
+                    .into_vec(),
             )),
+            #[cfg(feature = "treasure")]
             CompendiumCategory::Treasure => Ok(CategoryResult::Treasure(
-                self.fetch_data_for_specified_type(url)?,
+                self.fetch_paginated_data::<OneOrMany<_>>(url, None)?
+                    .into_vec(),
             )),
+            #[cfg(feature = "creatures")]
             CompendiumCategory::Creature => Ok(CategoryResult::Creatures(
-                self.fetch_data_for_specified_type(url)?,
+                self.fetch_paginated_data(url, None)?,
             )),
+            #[cfg(feature = "equipment")]
             CompendiumCategory::Equipment => Ok(CategoryResult::Equipment(
-                self.fetch_data_for_specified_type(url)?,
+                self.fetch_paginated_data::<OneOrMany<_>>(url, None)?
+                    .into_vec(),
             )),
+        }?;
+        if self.config.validate_category_types {
+            validate_category_types(category_path_for_type(&entry_type), &category_result)?;
+        }
+        if self.config.detect_duplicate_ids {
+            validate_unique_ids(category_result.entries())?;
         }
+        Ok(category_result)
     }
 
     fn fetch_data_for_specified_entry<T>(
@@ -229,72 +700,114 @@ impl CompendiumClient {
 
 impl CompendiumApiClient for CompendiumClient {
     fn entry(&self, identifier: EntryIdentifier) -> Result<EntryResponse> {
-        self.fetch_data_for_specified_entry(identifier, GameMode::Standard)
+        let cache = match &self.cache {
+            Some(cache) => cache,
+            None => return self.fetch_data_for_specified_entry(identifier, GameMode::Standard),
+        };
+        let key = entry_path(&identifier, GameMode::Standard);
+        if let Some(entry) = cache.get(&key) {
+            return Ok(entry);
+        }
+        let entry: EntryResponse =
+            self.fetch_data_for_specified_entry(identifier, GameMode::Standard)?;
+        cache.put(&key, entry.clone());
+        Ok(entry)
     }
 
+    #[cfg(feature = "monsters")]
     fn monster(&self, identifier: EntryIdentifier) -> Result<MonsterEntry> {
         self.fetch_data_for_specified_entry(identifier, GameMode::Standard)
     }
 
+    #[cfg(feature = "monsters")]
     fn master_mode_monster(&self, identifier: EntryIdentifier) -> Result<MonsterEntry> {
         self.fetch_data_for_specified_entry(identifier, GameMode::MasterMode)
     }
 
+    #[cfg(feature = "treasure")]
     fn treasure(&self, identifier: EntryIdentifier) -> Result<TreasureEntry> {
         self.fetch_data_for_specified_entry(identifier, GameMode::Standard)
     }
 
+    #[cfg(feature = "creatures")]
     fn creature(&self, identifier: EntryIdentifier) -> Result<CreatureEntry> {
         self.fetch_data_for_specified_entry(identifier, GameMode::Standard)
     }
 
+    #[cfg(feature = "equipment")]
     fn equipment(&self, identifier: EntryIdentifier) -> Result<EquipmentEntry> {
         self.fetch_data_for_specified_entry(identifier, GameMode::Standard)
     }
 
+    #[cfg(feature = "materials")]
     fn material(&self, identifier: EntryIdentifier) -> Result<MaterialEntry> {
         self.fetch_data_for_specified_entry(identifier, GameMode::Standard)
     }
 
     fn category(&self, category: CompendiumCategory) -> Result<CategoryResult> {
+        let category_url =
+            self.create_path(&self.base_url, category_path(category, GameMode::Standard))?;
+        self.fetch_data_for_specific_category(category_url, category)
+    }
+
+    fn master_mode_category(&self, category: CompendiumCategory) -> Result<CategoryResult> {
         let category_url = self.create_path(
             &self.base_url,
-            format!("category/{}", self.category_path_for_type(&category)),
+            category_path(category, GameMode::MasterMode),
         )?;
         self.fetch_data_for_specific_category(category_url, category)
     }
 
     fn all_entries(&self) -> Result<AllStandardEntries> {
         let all_normal_mode_entries_url = self.create_path(&self.base_url, "all")?;
-        self.fetch_data_for_specified_type(all_normal_mode_entries_url)
+        let all_entries: AllStandardEntries =
+            self.fetch_paginated_data(all_normal_mode_entries_url, self.config.bulk_timeout)?;
+        if self.config.detect_duplicate_ids {
+            validate_all_entries_unique_ids(&all_entries)?;
+        }
+        Ok(all_entries)
     }
 
+    #[cfg(feature = "monsters")]
     fn all_master_mode_entries(&self) -> Result<Vec<MonsterEntry>> {
         let all_master_mode_entries_url = self.create_path(&self.base_url, "master_mode/all")?;
-        self.fetch_data_for_specified_type(all_master_mode_entries_url)
+        self.fetch_paginated_data(all_master_mode_entries_url, self.config.bulk_timeout)
     }
 }
 
 impl CompendiumSealed for CompendiumClient {}
 
+/// How long to wait before the retry following the given attempt, doubling the base backoff for each attempt up to the maximum backoff
+fn backoff_delay(base_backoff: Duration, attempt: u32) -> Duration {
+    base_backoff
+        .checked_mul(2u32.saturating_pow(attempt))
+        .map_or(MAX_BACKOFF, |delay| delay.min(MAX_BACKOFF))
+}
+
+/// Whether the error may not happen again if the request is retried
+fn is_transient(error: &CompendiumError) -> bool {
+    matches!(
+        error,
+        CompendiumError::ServerError
+            | CompendiumError::ConnectionError(_)
+            | CompendiumError::Timeout(_)
+    )
+}
+
 fn handle_response(response_data: Response) -> Result<Response> {
-    let status_code = response_data.status();
-    // Would response_data.error_for_status() be better?
-    if status_code.is_server_error() {
-        return Err(CompendiumError::ServerError);
-    }
-    if status_code.is_client_error() {
-        return Err(CompendiumError::NoDataFound(
-            response_data.url().path().to_string(),
-        ));
-    }
+    check_status(response_data.status(), response_data.url())?;
     Ok(response_data)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use mockito::{mock, server_url, Mock};
+    use crate::cache::InMemoryCache;
+    use crate::domain::models::CompendiumEntry;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use mockito::{mock, server_url, Matcher, Mock};
+    use std::io::{Read, Write};
 
     fn silver_moblin_data<'a>() -> &'a str {
         r#"{"data":{"category":"monsters","common_locations":null,"description":"The strongest of all Moblins, Ganon's fiendish magic has allowed them to surpass even the Black Moblins in strength and resilience. They're called \"silver\" for both their body color as well as their rarity. The purple patterns on their bodies also help them to stand out.","drops":["moblin horn","moblin fang","moblin guts","amber","opal","topaz","ruby","sapphire","diamond"],"id":112,"image":"https://botw-compendium.herokuapp.com/api/v2/entry/silver_moblin/image","name":"silver moblin"}}"#
@@ -304,6 +817,26 @@ mod tests {
         r#"{"data":[{"category":"monsters","common_locations":null,"description":"The strongest of all Moblins, Ganon's fiendish magic has allowed them to surpass even the Black Moblins in strength and resilience. They're called \"silver\" for both their body color as well as their rarity. The purple patterns on their bodies also help them to stand out.","drops":["moblin horn","moblin fang","moblin guts","amber","opal","topaz","ruby","sapphire","diamond"],"id":112,"image":"https://botw-compendium.herokuapp.com/api/v2/entry/silver_moblin/image","name":"silver moblin"}]}"#
     }
 
+    fn paginated_monster_category_data<'a>() -> &'a str {
+        r#"{"data":[{"category":"monsters","common_locations":null,"description":"The strongest of all Moblins, Ganon's fiendish magic has allowed them to surpass even the Black Moblins in strength and resilience. They're called \"silver\" for both their body color as well as their rarity. The purple patterns on their bodies also help them to stand out.","drops":["moblin horn","moblin fang","moblin guts","amber","opal","topaz","ruby","sapphire","diamond"],"id":112,"image":"https://botw-compendium.herokuapp.com/api/v2/entry/silver_moblin/image","name":"silver moblin"}],"next":"category/monsters/2"}"#
+    }
+
+    fn bokoblin_category_page_data<'a>() -> &'a str {
+        r#"{"data":[{"category":"monsters","common_locations":["Hyrule Field","Great Hyrule Forest"],"description":"A common species of monster found throughout Hyrule.","drops":["bokoblin horn","bokoblin fang"],"id":104,"image":"https://botw-compendium.herokuapp.com/api/v2/entry/bokoblin/image","name":"bokoblin"}]}"#
+    }
+
+    fn duplicate_monster_category_data<'a>() -> &'a str {
+        r#"{"data":[{"category":"monsters","common_locations":null,"description":"The strongest of all Moblins.","drops":["moblin horn"],"id":112,"image":"https://botw-compendium.herokuapp.com/api/v2/entry/silver_moblin/image","name":"silver moblin"},{"category":"monsters","common_locations":null,"description":"The strongest of all Moblins.","drops":["moblin horn"],"id":112,"image":"https://botw-compendium.herokuapp.com/api/v2/entry/silver_moblin/image","name":"silver moblin"}]}"#
+    }
+
+    fn all_entries_data<'a>() -> &'a str {
+        r#"{"data":{"creatures":{"food":[],"non_food":[]},"equipment":[],"materials":[],"monsters":[{"category":"monsters","common_locations":null,"description":"The strongest of all Moblins.","drops":["moblin horn"],"id":112,"image":"https://botw-compendium.herokuapp.com/api/v2/entry/silver_moblin/image","name":"silver moblin"},{"category":"monsters","common_locations":["Hyrule Field"],"description":"A common species of monster found throughout Hyrule.","drops":["bokoblin horn"],"id":104,"image":"https://botw-compendium.herokuapp.com/api/v2/entry/bokoblin/image","name":"bokoblin"}],"treasure":[]}}"#
+    }
+
+    fn mismatched_category_data<'a>() -> &'a str {
+        r#"{"data":[{"category":"creatures","common_locations":["Hyrule Ridge","Tabantha Frontier"],"cooking_effect":"heat resistance","description":"The powdery scales of this butterfly's wings cool the air around it. Watching it flutter around snowflakes is a thing of beauty. Cook it with monster parts for a heat-resistant elixir.","hearts_recovered":0,"id":67,"image":"https://botw-compendium.herokuapp.com/api/v2/entry/winterwing_butterfly/image","name":"winterwing butterfly"}]}"#
+    }
+
     fn winterwing_butterfly_data<'a>() -> &'a str {
         r#"{"data":{"category":"creatures","common_locations":["Hyrule Ridge","Tabantha Frontier"],"cooking_effect":"heat resistance","description":"The powdery scales of this butterfly's wings cool the air around it. Watching it flutter around snowflakes is a thing of beauty. Cook it with monster parts for a heat-resistant elixir.","hearts_recovered":0,"id":67,"image":"https://botw-compendium.herokuapp.com/api/v2/entry/winterwing_butterfly/image","name":"winterwing butterfly"}}"#
     }
@@ -340,6 +873,14 @@ mod tests {
         CompendiumClient::new(server_url().as_str()).unwrap()
     }
 
+    fn create_validating_compendium() -> CompendiumClient {
+        CompendiumClient::builder()
+            .base_url(server_url().as_str())
+            .validate_category_types(true)
+            .build()
+            .unwrap()
+    }
+
     #[test]
     fn test_compendium_client_monster_entry_search() {
         let mock = create_successful_mock("/entry/silver_moblin", silver_moblin_data());
@@ -355,6 +896,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_compendium_client_normalizes_entry_names() {
+        let mock = create_successful_mock("/entry/silver_moblin", silver_moblin_data());
+        let compendium = create_compendium();
+        let monster = compendium
+            .monster(EntryIdentifier::Name(" Silver  Moblin "))
+            .unwrap();
+        assert_eq!(112, monster.id());
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_monster_search_by_owned_name() {
+        let mock = create_successful_mock("/entry/silver_moblin", silver_moblin_data());
+        let compendium = create_compendium();
+        let name = format!("{} moblin", "silver");
+        let monster = compendium.monster(name.into()).unwrap();
+        assert_eq!(112, monster.id());
+        mock.assert()
+    }
+
     #[test]
     fn test_compendium_client_creature_entry_search() {
         let mock =
@@ -372,6 +934,48 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_compendium_client_entry_summary_search() {
+        let mock = create_successful_mock("/entry/silver_moblin", silver_moblin_data());
+        let compendium = create_compendium();
+        let identifier = EntryIdentifier::Name("silver_moblin");
+        let summary = compendium.entry_summary(identifier).unwrap();
+        assert_eq!(112, summary.id());
+        assert_eq!("silver moblin", summary.name());
+        assert_eq!("monsters", summary.category());
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_entry_with_fallback_search() {
+        let missing_mock = create_missing_data_mock("/entry/999");
+        let mock = create_successful_mock("/entry/silver_moblin", silver_moblin_data());
+        let compendium = create_compendium();
+        let result = compendium
+            .entry_with_fallback(
+                EntryIdentifier::Id(999),
+                EntryIdentifier::Name("silver moblin"),
+            )
+            .unwrap();
+        assert_eq!(112, result.id());
+        missing_mock.assert();
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_entry_with_fallback_only_on_missing_data() {
+        let server_error_mock = create_server_error_data_mock("/entry/112", silver_moblin_data());
+        let mock = create_successful_mock("/entry/silver_moblin", silver_moblin_data()).expect(0);
+        let compendium = create_compendium();
+        let result = compendium.entry_with_fallback(
+            EntryIdentifier::Id(112),
+            EntryIdentifier::Name("silver moblin"),
+        );
+        assert!(matches!(result, Err(CompendiumError::ServerError)));
+        server_error_mock.assert();
+        mock.assert()
+    }
+
     #[test]
     fn test_compendium_client_monster_category_search() {
         let mock = create_successful_mock("/category/monsters", monster_category_data());
@@ -388,29 +992,1206 @@ mod tests {
     }
 
     #[test]
-    fn test_compendium_client_missing_monster_response() {
-        let mock = create_missing_data_mock("/entry/example_monster");
-        let compendium = create_compendium();
-        let identifier = EntryIdentifier::Name("example_monster");
-        assert!(compendium.entry(identifier).is_err());
+    fn test_compendium_client_base_url() {
+        assert_eq!(
+            "https://botw-compendium.herokuapp.com/api/v2/",
+            CompendiumClient::default().base_url().as_str()
+        );
+    }
+
+    #[test]
+    fn test_compendium_client_config_from_builder() {
+        let compendium = create_validating_compendium();
+        let config = compendium.config();
+        assert_eq!(server_url(), config.base_url());
+        assert!(config.validate_category_types());
+        assert!(!CompendiumClient::default()
+            .config()
+            .validate_category_types());
+    }
+
+    #[test]
+    fn test_compendium_client_all_entries_with_bulk_timeout() {
+        let mock = create_successful_mock("/master_mode/all", monster_category_data());
+        let compendium = CompendiumClient::builder()
+            .base_url(server_url().as_str())
+            .timeout(Duration::from_secs(1))
+            .bulk_timeout(Duration::from_secs(60))
+            .build()
+            .unwrap();
+        assert_eq!(Some(Duration::from_secs(1)), compendium.config().timeout());
+        assert_eq!(
+            Some(Duration::from_secs(60)),
+            compendium.config().bulk_timeout()
+        );
+        assert_eq!(1, compendium.all_master_mode_entries().unwrap().len());
         mock.assert()
     }
 
     #[test]
-    fn test_compendium_client_internal_server_error_response() {
-        let mock = create_server_error_data_mock("/entry/silver_moblin", silver_moblin_data());
-        let compendium = create_compendium();
-        let identifier = EntryIdentifier::Name("silver_moblin");
-        assert!(compendium.entry(identifier).is_err());
+    fn test_compendium_client_for_tears_of_the_kingdom() {
+        let compendium = CompendiumClient::for_version(GameVersion::TearsOfTheKingdom).unwrap();
+        assert_eq!(
+            "https://botw-compendium.herokuapp.com/api/v3/compendium/",
+            compendium.base_url().as_str()
+        );
+        assert_eq!(
+            GameVersion::TearsOfTheKingdom,
+            compendium.config().game_version()
+        );
+        assert_eq!(
+            GameVersion::BreathOfTheWild,
+            CompendiumClient::default().config().game_version()
+        );
+    }
+
+    #[test]
+    fn test_compendium_client_tears_of_the_kingdom_paths() {
+        let entry_mock = create_successful_mock(
+            "/api/v3/compendium/entry/silver_moblin?game=totk",
+            silver_moblin_data(),
+        );
+        let category_mock = create_successful_mock(
+            "/api/v3/compendium/category/monsters?game=totk",
+            monster_category_data(),
+        );
+        let compendium = CompendiumClient::builder()
+            .game_version(GameVersion::TearsOfTheKingdom)
+            .base_url(&format!("{}/api/v3/compendium/", server_url()))
+            .build()
+            .unwrap();
+        compendium
+            .monster(EntryIdentifier::Name("silver moblin"))
+            .unwrap();
+        compendium.category(CompendiumCategory::Monster).unwrap();
+        entry_mock.assert();
+        category_mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_user_agent_from_builder() {
+        let mock = mock("GET", "/entry/silver_moblin")
+            .match_header("user-agent", "hyrule-tracker/1.0")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(silver_moblin_data())
+            .create();
+        let compendium = CompendiumClient::builder()
+            .base_url(server_url().as_str())
+            .user_agent("hyrule-tracker/1.0")
+            .build()
+            .unwrap();
+        assert_eq!("hyrule-tracker/1.0", compendium.config().user_agent());
+        compendium
+            .entry(EntryIdentifier::Name("silver moblin"))
+            .unwrap();
         mock.assert()
     }
 
     #[test]
-    fn test_compendium_client_unexpected_server_response() {
-        let mock = create_successful_mock("/entry/silver_moblin", missing_data_response());
+    fn test_compendium_client_decompresses_gzip_responses() {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(silver_moblin_data().as_bytes()).unwrap();
+        let mock = mock("GET", "/entry/silver_moblin")
+            .match_header("accept-encoding", Matcher::Regex("gzip".to_string()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_header("content-encoding", "gzip")
+            .with_body(encoder.finish().unwrap())
+            .create();
         let compendium = create_compendium();
-        let identifier = EntryIdentifier::Name("silver_moblin");
-        assert!(compendium.entry(identifier).is_err());
+        assert!(compendium.config().compression());
+        let monster = compendium
+            .monster(EntryIdentifier::Name("silver moblin"))
+            .unwrap();
+        assert_eq!(112, monster.id());
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_without_compression() {
+        let mock = mock("GET", "/entry/silver_moblin")
+            .match_header("accept-encoding", Matcher::Missing)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(silver_moblin_data())
+            .create();
+        let compendium = CompendiumClient::builder()
+            .base_url(server_url().as_str())
+            .compression(false)
+            .build()
+            .unwrap();
+        assert!(!compendium.config().compression());
+        compendium
+            .monster(EntryIdentifier::Name("silver moblin"))
+            .unwrap();
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_default_user_agent() {
+        let mock = mock("GET", "/entry/silver_moblin")
+            .match_header(
+                "user-agent",
+                concat!("rusty_hyrule_compendium/", env!("CARGO_PKG_VERSION")),
+            )
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(silver_moblin_data())
+            .create();
+        create_compendium()
+            .entry(EntryIdentifier::Name("silver moblin"))
+            .unwrap();
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_with_user_agent() {
+        let mock = mock("GET", "/entry/silver_moblin")
+            .match_header("user-agent", "hyrule-tracker/2.0")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(silver_moblin_data())
+            .create();
+        CompendiumClient::with_user_agent(server_url().as_str(), "hyrule-tracker/2.0")
+            .unwrap()
+            .entry(EntryIdentifier::Name("silver moblin"))
+            .unwrap();
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_custom_http_client() {
+        let mock = mock("GET", "/entry/silver_moblin")
+            .match_header("x-shared-client", "true")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(silver_moblin_data())
+            .create();
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert(
+            "x-shared-client",
+            reqwest::header::HeaderValue::from_static("true"),
+        );
+        let http_client = Client::builder().default_headers(headers).build().unwrap();
+        let compendium = CompendiumClient::builder()
+            .base_url(server_url().as_str())
+            .http_client(http_client)
+            .build()
+            .unwrap();
+        compendium
+            .entry(EntryIdentifier::Name("silver moblin"))
+            .unwrap();
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_sends_requests_through_proxy() {
+        let mock = mock("GET", Matcher::Regex("entry/silver_moblin$".to_string()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(silver_moblin_data())
+            .create();
+        let compendium = CompendiumClient::builder()
+            .base_url("http://botw-compendium.invalid/api/v2/")
+            .proxy(server_url().as_str())
+            .build()
+            .unwrap();
+        assert_eq!(Some(server_url().as_str()), compendium.config().proxy());
+        compendium
+            .entry(EntryIdentifier::Name("silver moblin"))
+            .unwrap();
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_proxy_credentials_are_not_kept() {
+        let compendium = CompendiumClient::builder()
+            .proxy("http://user:pw@host:8080")
+            .build()
+            .unwrap();
+        assert_eq!(Some("http://host:8080"), compendium.config().proxy());
+        let serialized = serde_json::to_string(compendium.config()).unwrap();
+        assert!(serialized.contains("http://host:8080"));
+        assert!(!serialized.contains("pw"));
+        assert!(!format!("{:?}", compendium).contains("pw"));
+        let builder = CompendiumClient::builder().proxy("user:pw@host:8080");
+        assert!(!format!("{:?}", builder).contains("pw"));
+    }
+
+    #[test]
+    fn test_compendium_client_invalid_proxy() {
+        let result = CompendiumClient::builder().proxy("not a proxy url").build();
+        assert!(matches!(
+            result,
+            Err(CompendiumError::ClientConfigurationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_compendium_client_rate_limits_requests() {
+        let mock = create_successful_mock("/entry/112", silver_moblin_data()).expect(4);
+        let compendium = CompendiumClient::builder()
+            .base_url(server_url().as_str())
+            .requests_per_second(10)
+            .build()
+            .unwrap();
+        assert_eq!(Some(10), compendium.config().requests_per_second());
+        let started = std::time::Instant::now();
+        for _ in 0..4 {
+            compendium.monster(EntryIdentifier::Id(112)).unwrap();
+        }
+        // The first request is made straight away, the other three 100ms apart
+        assert!(started.elapsed() >= Duration::from_millis(300));
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_requests_per_second_defaults_to_unlimited() {
+        assert_eq!(
+            None,
+            CompendiumClient::default().config().requests_per_second()
+        );
+        let compendium = CompendiumClient::builder()
+            .requests_per_second(0)
+            .build()
+            .unwrap();
+        assert_eq!(None, compendium.config().requests_per_second());
+    }
+
+    #[test]
+    fn test_compendium_client_with_client() {
+        let mock = mock("GET", "/entry/silver_moblin")
+            .match_header("user-agent", "shared-client/1.0")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(silver_moblin_data())
+            .create();
+        let http_client = Client::builder()
+            .user_agent("shared-client/1.0")
+            .build()
+            .unwrap();
+        let compendium = CompendiumClient::with_client(server_url().as_str(), http_client).unwrap();
+        compendium
+            .entry(EntryIdentifier::Name("silver moblin"))
+            .unwrap();
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_with_client_invalid_base_url() {
+        let result = CompendiumClient::with_client("not a url", Client::new());
+        assert!(matches!(result, Err(CompendiumError::InvalidBaseUrl(_))));
+    }
+
+    #[test]
+    fn test_compendium_client_invalid_base_url_from_builder() {
+        assert!(matches!(
+            CompendiumClient::builder().base_url("not a url").build(),
+            Err(CompendiumError::InvalidBaseUrl(url)) if url == "not a url"
+        ));
+    }
+
+    fn create_retrying_compendium(retries: u32) -> CompendiumClient {
+        CompendiumClient::builder()
+            .base_url(server_url().as_str())
+            .retries(retries)
+            .base_backoff(Duration::from_millis(1))
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_compendium_client_retries_server_errors() {
+        let server_error_mock = mock("GET", "/entry/silver_moblin")
+            .with_status(500)
+            .expect(2)
+            .create();
+        let mock = create_successful_mock("/entry/silver_moblin", silver_moblin_data());
+        let compendium = create_retrying_compendium(2);
+        let monster = compendium
+            .monster(EntryIdentifier::Name("silver moblin"))
+            .unwrap();
+        assert_eq!(112, monster.id());
+        server_error_mock.assert();
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_returns_last_error_after_retries() {
+        let mock = create_server_error_data_mock("/entry/silver_moblin", missing_data_response())
+            .expect(3);
+        let compendium = create_retrying_compendium(2);
+        assert!(matches!(
+            compendium.monster(EntryIdentifier::Name("silver moblin")),
+            Err(CompendiumError::ServerError)
+        ));
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_does_not_retry_missing_data() {
+        let mock = create_missing_data_mock("/entry/example_monster").expect(1);
+        let compendium = create_retrying_compendium(2);
+        assert!(matches!(
+            compendium.monster(EntryIdentifier::Name("example_monster")),
+            Err(CompendiumError::NoDataFound(_))
+        ));
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_request_interceptor() {
+        let mock = mock("GET", "/entry/silver_moblin?source=test")
+            .match_header("x-request-source", "test")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(silver_moblin_data())
+            .create();
+        let compendium = CompendiumClient::builder()
+            .base_url(server_url().as_str())
+            .request_interceptor(|request| {
+                request
+                    .header("X-Request-Source", "test")
+                    .query(&[("source", "test")])
+            })
+            .build()
+            .unwrap();
+        let monster = compendium
+            .monster(EntryIdentifier::Name("silver moblin"))
+            .unwrap();
+        assert_eq!(112, monster.id());
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_entry_cache() {
+        let mock = create_successful_mock("/entry/silver_moblin", silver_moblin_data()).expect(1);
+        let cache = Arc::new(InMemoryCache::new());
+        let compendium = CompendiumClient::builder()
+            .base_url(server_url().as_str())
+            .cache(cache.clone())
+            .build()
+            .unwrap();
+        let identifier = EntryIdentifier::Name("silver moblin");
+        assert_eq!(112, compendium.entry(identifier.clone()).unwrap().id());
+        assert_eq!(112, compendium.entry(identifier).unwrap().id());
+        assert!(cache.get("entry/silver_moblin").is_some());
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_warm_cache() {
+        let found_mock =
+            create_successful_mock("/entry/silver_moblin", silver_moblin_data()).expect(1);
+        let missing_mock = create_missing_data_mock("/entry/missing").expect(1);
+        let cache = Arc::new(InMemoryCache::new());
+        let compendium = CompendiumClient::builder()
+            .base_url(server_url().as_str())
+            .cache(cache.clone())
+            .build()
+            .unwrap();
+        let mut progress = Vec::new();
+        let results = compendium.warm_cache(
+            &[
+                EntryIdentifier::Name("silver moblin"),
+                EntryIdentifier::Name("missing"),
+            ],
+            FailurePolicy::CollectErrors,
+            |done, total| progress.push((done, total)),
+        );
+        assert_eq!(vec![(1, 2), (2, 2)], progress);
+        assert_eq!(112, results[0].as_ref().unwrap().id());
+        assert!(matches!(results[1], Err(CompendiumError::NoDataFound(_))));
+        assert!(cache.get("entry/silver_moblin").is_some());
+        found_mock.assert();
+        missing_mock.assert()
+    }
+
+    #[cfg(feature = "scraping")]
+    #[test]
+    fn test_compendium_client_entry_from_html() {
+        let mock = mock("GET", "/web/entry/silver_moblin")
+            .with_status(200)
+            .with_header("content-type", "text/html")
+            .with_body(r#"<article class="entry" data-id="112" data-category="monsters"><h1 class="entry-name">silver moblin</h1><img class="entry-image" src=""><p class="entry-description">The strongest of all Moblins.</p></article>"#)
+            .create();
+        let compendium = CompendiumClient::builder()
+            .base_url(server_url().as_str())
+            .web_url(&format!("{}/web/", server_url()))
+            .build()
+            .unwrap();
+        let entry = compendium
+            .entry_from_html(EntryIdentifier::Name("silver moblin"))
+            .unwrap();
+        assert_eq!(112, entry.id());
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_fetch_path() {
+        let mock = create_successful_mock("/regions", r#"{"data":["Central Hyrule","Hebra"]}"#);
+        let compendium = create_compendium();
+        let regions: Vec<String> = compendium.fetch_path("regions").unwrap();
+        assert_eq!(vec!["Central Hyrule", "Hebra"], regions);
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_entries() {
+        let moblin_mock = create_successful_mock("/entry/112", silver_moblin_data());
+        let butterfly_mock = create_successful_mock("/entry/67", winterwing_butterfly_data());
+        let missing_mock = create_missing_data_mock("/entry/missing");
+        let compendium = create_compendium();
+        let results = compendium.entries(&[
+            EntryIdentifier::Id(67),
+            EntryIdentifier::Name("missing"),
+            EntryIdentifier::Id(112),
+        ]);
+        assert_eq!(3, results.len());
+        assert_eq!(67, results[0].as_ref().unwrap().id());
+        assert!(matches!(results[1], Err(CompendiumError::NoDataFound(_))));
+        assert_eq!(112, results[2].as_ref().unwrap().id());
+        assert!(compendium.entries(&[]).is_empty());
+        moblin_mock.assert();
+        butterfly_mock.assert();
+        missing_mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_warm_cache_aborts_on_first_failure() {
+        let missing_mock = create_missing_data_mock("/entry/missing").expect(1);
+        let compendium = create_compendium();
+        let mut progress = Vec::new();
+        let results = compendium.warm_cache(
+            &[EntryIdentifier::Name("missing"), EntryIdentifier::Id(112)],
+            FailurePolicy::AbortOnFirst,
+            |done, total| progress.push((done, total)),
+        );
+        assert_eq!(vec![(1, 2)], progress);
+        assert_eq!(1, results.len());
+        missing_mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_base_url_without_trailing_slash() {
+        let mock = create_successful_mock("/api/v2/entry/silver_moblin", silver_moblin_data());
+        let compendium = CompendiumClient::new(&format!("{}/api/v2", server_url())).unwrap();
+        assert_eq!(
+            format!("{}/api/v2/", server_url()),
+            compendium.base_url().as_str()
+        );
+        let entry = compendium
+            .entry(EntryIdentifier::Name("silver moblin"))
+            .unwrap();
+        assert_eq!(112, entry.id());
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_all_entries_streamed() {
+        let mock = create_successful_mock("/all", all_entries_data());
+        let compendium = create_compendium();
+        let mut ids = Vec::new();
+        compendium
+            .all_entries_streamed(|entry| ids.push(entry.id()))
+            .unwrap();
+        assert_eq!(vec![112, 104], ids);
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_all_entries_streamed_follows_pages() {
+        let first_page = create_successful_mock(
+            "/all",
+            r#"{"data":{"creatures":{"food":[],"non_food":[]},"monsters":[{"category":"monsters","common_locations":null,"description":"","drops":null,"id":112,"image":"","name":"silver moblin"}]},"next":"all/2"}"#,
+        );
+        let second_page = create_successful_mock(
+            "/all/2",
+            r#"{"data":{"creatures":{"food":{"category":"creatures","common_locations":null,"cooking_effect":"","description":"","hearts_recovered":0,"id":1,"image":"","name":"horse"},"non_food":[]},"treasure":[]}}"#,
+        );
+        let compendium = create_compendium();
+        let mut entries = Vec::new();
+        compendium
+            .all_entries_streamed(|entry| entries.push(entry))
+            .unwrap();
+        assert_eq!(2, entries.len());
+        assert!(matches!(entries[0], EntryResponse::Monster(_)));
+        assert!(matches!(entries[1], EntryResponse::Creature(_)));
+        first_page.assert();
+        second_page.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_all_entries_streamed_detects_duplicate_ids() {
+        let mock = create_successful_mock(
+            "/all",
+            r#"{"data":{"monsters":[{"category":"monsters","common_locations":null,"description":"","drops":null,"id":112,"image":"","name":"silver moblin"},{"category":"monsters","common_locations":null,"description":"","drops":null,"id":112,"image":"","name":"silver moblin"}]}}"#,
+        );
+        let compendium = CompendiumClient::builder()
+            .base_url(server_url().as_str())
+            .detect_duplicate_ids(true)
+            .build()
+            .unwrap();
+        let mut count = 0;
+        let result = compendium.all_entries_streamed(|_| count += 1);
+        assert!(matches!(result, Err(CompendiumError::DuplicateId(112))));
+        assert_eq!(1, count);
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_all_entries_streamed_invalid_response() {
+        let mock = create_successful_mock("/all", r#"{"data":{"monsters":[{"id":112}]}}"#);
+        let compendium = create_compendium();
+        let result = compendium.all_entries_streamed(|_| {});
+        assert!(matches!(
+            result,
+            Err(CompendiumError::DeserializationError(_))
+        ));
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_all_entries_via_categories() {
+        let empty_mocks: Vec<Mock> = ["equipment", "materials", "treasure"]
+            .iter()
+            .map(|category| {
+                create_successful_mock(&format!("/category/{}", category), r#"{"data":[]}"#)
+            })
+            .collect();
+        let monsters_mock = create_successful_mock("/category/monsters", monster_category_data());
+        let creatures_mock =
+            create_server_error_data_mock("/category/creatures", missing_data_response()).expect(2);
+        let compendium = create_compendium();
+
+        let (all_entries, errors) = compendium
+            .all_entries_via_categories(FailurePolicy::CollectErrors)
+            .unwrap();
+        assert_eq!(1, all_entries.monsters().len());
+        assert!(all_entries.creatures().food().is_empty());
+        assert_eq!(1, errors.len());
+        assert!(matches!(errors[0], CompendiumError::ServerError));
+
+        assert!(matches!(
+            compendium.all_entries_via_categories(FailurePolicy::AbortOnFirst),
+            Err(CompendiumError::ServerError)
+        ));
+        monsters_mock.assert();
+        creatures_mock.assert();
+        for mock in empty_mocks {
+            mock.assert();
+        }
+    }
+
+    #[test]
+    fn test_compendium_client_connect_timeout() {
+        let mock = create_successful_mock("/entry/silver_moblin", silver_moblin_data());
+        let compendium = CompendiumClient::builder()
+            .base_url(server_url().as_str())
+            .connect_timeout(Duration::from_secs(30))
+            .timeout(Duration::from_secs(5))
+            .build()
+            .unwrap();
+        assert_eq!(
+            Some(Duration::from_secs(30)),
+            compendium.config().connect_timeout()
+        );
+        assert!(compendium
+            .monster(EntryIdentifier::Name("silver moblin"))
+            .is_ok());
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_timeout_error() {
+        // The listener accepts connections but never responds, so every request times out
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let compendium = CompendiumClient::builder()
+            .base_url(&format!("http://{}/", listener.local_addr().unwrap()))
+            .timeout(Duration::from_millis(100))
+            .build()
+            .unwrap();
+        let result = compendium.entry(EntryIdentifier::Id(112));
+        assert!(matches!(result, Err(CompendiumError::Timeout(_))));
+    }
+
+    #[test]
+    fn test_backoff_delay_doubles_up_to_the_maximum() {
+        let base_backoff = Duration::from_millis(500);
+        assert_eq!(Duration::from_millis(500), backoff_delay(base_backoff, 0));
+        assert_eq!(Duration::from_secs(2), backoff_delay(base_backoff, 2));
+        assert_eq!(Duration::from_secs(16), backoff_delay(base_backoff, 5));
+        assert_eq!(MAX_BACKOFF, backoff_delay(base_backoff, 6));
+        assert_eq!(MAX_BACKOFF, backoff_delay(base_backoff, 19));
+        assert_eq!(MAX_BACKOFF, backoff_delay(base_backoff, u32::MAX));
+        assert_eq!(MAX_BACKOFF, backoff_delay(Duration::MAX, 1));
+    }
+
+    /// Serve the given number of connections with a response whose body stops part way through,
+    /// holding each connection open for `hold` before closing it, returning the base url to request
+    fn serve_partial_bodies(
+        connections: usize,
+        hold: Duration,
+    ) -> (String, thread::JoinHandle<()>) {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}/", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            for _ in 0..connections {
+                let (mut connection, _) = listener.accept().unwrap();
+                let mut buffer = [0; 1024];
+                let read = connection.read(&mut buffer).unwrap();
+                assert!(read > 0);
+                connection
+                    .write_all(b"HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: 1000\r\n\r\n{\"data\":[")
+                    .unwrap();
+                thread::sleep(hold);
+            }
+        });
+        (base_url, server)
+    }
+
+    #[test]
+    fn test_compendium_client_body_timeout_is_a_timeout() {
+        let (base_url, server) = serve_partial_bodies(1, Duration::from_millis(500));
+        let compendium = CompendiumClient::builder()
+            .base_url(&base_url)
+            .timeout(Duration::from_millis(100))
+            .build()
+            .unwrap();
+        let result = compendium.category(CompendiumCategory::Monster);
+        assert!(matches!(result, Err(CompendiumError::Timeout(_))));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_compendium_client_body_connection_failure_is_retried() {
+        let (base_url, server) = serve_partial_bodies(2, Duration::ZERO);
+        let compendium = CompendiumClient::builder()
+            .base_url(&base_url)
+            .retries(1)
+            .base_backoff(Duration::from_millis(10))
+            .build()
+            .unwrap();
+        let result = compendium.category(CompendiumCategory::Monster);
+        assert!(matches!(result, Err(CompendiumError::ConnectionError(_))));
+        // The server only returns once it has served the retried request too
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_compendium_client_wake_and_fetch() {
+        let mock = create_successful_mock("/entry/silver_moblin", silver_moblin_data()).expect(1);
+        let compendium = create_compendium();
+        let entry = compendium
+            .wake_and_fetch(EntryIdentifier::Name("silver moblin"))
+            .unwrap();
+        assert_eq!(112, entry.id());
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_wake_and_fetch_waits_before_retrying() {
+        let unused_address = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let compendium = CompendiumClient::builder()
+            .base_url(&format!("http://{}/", unused_address))
+            .wake_up_delay(Duration::from_millis(200))
+            .build()
+            .unwrap();
+        let start = std::time::Instant::now();
+        let result = compendium.wake_and_fetch(EntryIdentifier::Id(112));
+        assert!(matches!(result, Err(CompendiumError::ConnectionError(_))));
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+
+    #[test]
+    fn test_compendium_client_category_mismatch_is_tolerated_by_default() {
+        let mock = create_successful_mock("/category/monsters", mismatched_category_data());
+        let compendium = create_compendium();
+        assert!(compendium.category(CompendiumCategory::Monster).is_ok());
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_category_mismatch_when_validating() {
+        let mock = create_successful_mock("/category/monsters", mismatched_category_data());
+        let compendium = create_validating_compendium();
+        match compendium.category(CompendiumCategory::Monster) {
+            Err(CompendiumError::CategoryMismatch { expected, found }) => {
+                assert_eq!("monsters", expected);
+                assert_eq!("creatures", found);
+                mock.assert()
+            }
+            _ => panic!("Expected a category mismatch for the monster category"),
+        }
+    }
+
+    #[test]
+    fn test_compendium_client_matching_category_when_validating() {
+        let mock = create_successful_mock("/category/monsters", monster_category_data());
+        let compendium = create_validating_compendium();
+        assert!(compendium.category(CompendiumCategory::Monster).is_ok());
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_paginated_category_search() {
+        let first_page =
+            create_successful_mock("/category/monsters", paginated_monster_category_data());
+        let second_page =
+            create_successful_mock("/category/monsters/2", bokoblin_category_page_data());
+        let compendium = create_compendium();
+        let result = compendium.category(CompendiumCategory::Monster).unwrap();
+        match result {
+            CategoryResult::Monsters(monsters) => {
+                let ids: Vec<i32> = monsters.iter().map(MonsterEntry::id).collect();
+                assert_eq!(vec![112, 104], ids);
+                first_page.assert();
+                second_page.assert()
+            }
+            _ => panic!("Unexpected result while search for monster category"),
+        }
+    }
+
+    #[test]
+    fn test_compendium_client_pagination_is_capped() {
+        let first_page =
+            create_successful_mock("/category/monsters", paginated_monster_category_data())
+                .expect(1);
+        let looping_page = mock("GET", "/category/monsters/2")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(r#"{"data":[],"next":"category/monsters/2"}"#)
+            .expect(MAX_PAGES - 1)
+            .create();
+        let compendium = create_compendium();
+        assert!(matches!(
+            compendium.category(CompendiumCategory::Monster),
+            Err(CompendiumError::PageLimitExceeded(MAX_PAGES))
+        ));
+        first_page.assert();
+        looping_page.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_duplicate_ids_when_detecting() {
+        let mock = create_successful_mock("/category/monsters", duplicate_monster_category_data());
+        let compendium = CompendiumClient::builder()
+            .base_url(server_url().as_str())
+            .detect_duplicate_ids(true)
+            .build()
+            .unwrap();
+        assert!(matches!(
+            compendium.category(CompendiumCategory::Monster),
+            Err(CompendiumError::DuplicateId(112))
+        ));
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_duplicate_ids_are_tolerated_by_default() {
+        let mock = create_successful_mock("/category/monsters", duplicate_monster_category_data());
+        let compendium = create_compendium();
+        match compendium.category(CompendiumCategory::Monster).unwrap() {
+            CategoryResult::Monsters(monsters) => {
+                assert_eq!(2, monsters.len());
+                mock.assert()
+            }
+            _ => panic!("Unexpected result while search for monster category"),
+        }
+    }
+
+    #[test]
+    fn test_compendium_client_unexpected_category_response() {
+        let mock = create_successful_mock("/category/monsters", missing_data_response());
+        let compendium = create_compendium();
+        assert!(matches!(
+            compendium.category(CompendiumCategory::Monster),
+            Err(CompendiumError::NoDataFound(path)) if path == "/category/monsters"
+        ));
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_all_entries_without_results() {
+        let mock = create_successful_mock("/all", missing_data_response()).expect(2);
+        let compendium = create_compendium();
+        assert!(matches!(
+            compendium.all_entries(),
+            Err(CompendiumError::NoDataFound(path)) if path == "/all"
+        ));
+        assert!(matches!(
+            compendium.all_entries_streamed(|_| {}),
+            Err(CompendiumError::NoDataFound(path)) if path == "/all"
+        ));
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_malformed_category_is_not_treated_as_missing() {
+        let mock =
+            create_successful_mock("/category/monsters", r#"{"data":{"id":"not a number"}}"#);
+        let compendium = create_compendium();
+        assert!(matches!(
+            compendium.category(CompendiumCategory::Monster),
+            Err(CompendiumError::DeserializationError(_))
+        ));
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_category_accepts_single_entry_object() {
+        let entry = |category: &str, extra: serde_json::Value| {
+            let mut entry = serde_json::json!({
+                "category": category,
+                "common_locations": null,
+                "description": "",
+                "id": 1,
+                "image": "",
+                "name": "entry"
+            });
+            entry
+                .as_object_mut()
+                .unwrap()
+                .extend(extra.as_object().unwrap().clone());
+            entry
+        };
+        let drops = serde_json::json!({ "drops": ["amber"] });
+        let cases = [
+            (
+                CompendiumCategory::Monster,
+                "monsters",
+                entry("monsters", drops.clone()),
+            ),
+            (
+                CompendiumCategory::Treasure,
+                "treasure",
+                entry("treasure", drops),
+            ),
+            (
+                CompendiumCategory::Material,
+                "materials",
+                entry("materials", serde_json::json!({ "hearts_recovered": 1.0 })),
+            ),
+            (
+                CompendiumCategory::Equipment,
+                "equipment",
+                entry(
+                    "equipment",
+                    serde_json::json!({ "attack": 1, "defense": 0 }),
+                ),
+            ),
+        ];
+        let compendium = create_compendium();
+        for (category, path, entry) in cases {
+            for data in [entry.clone(), serde_json::json!([entry])] {
+                let body = serde_json::json!({ "data": data }).to_string();
+                let mock = create_successful_mock(&format!("/category/{}", path), &body);
+                let result = compendium.category(category).unwrap();
+                assert_eq!(
+                    vec![1],
+                    result.entries().map(|entry| entry.id()).collect::<Vec<_>>()
+                );
+                mock.assert();
+            }
+        }
+
+        let creature = entry("creatures", serde_json::json!({ "drops": null }));
+        for food in [creature.clone(), serde_json::json!([creature])] {
+            let body = serde_json::json!({ "data": { "food": food, "non_food": [] } }).to_string();
+            let mock = create_successful_mock("/category/creatures", &body);
+            match compendium.category(CompendiumCategory::Creature).unwrap() {
+                CategoryResult::Creatures(creatures) => assert_eq!(1, creatures.food().len()),
+                _ => panic!("expected creatures"),
+            }
+            mock.assert();
+        }
+    }
+
+    #[test]
+    fn test_compendium_client_download_image() {
+        let mock = mock("GET", "/entry/silver_moblin/image")
+            .with_status(200)
+            .with_header("content-type", "image/png")
+            .with_body([137, 80, 78, 71])
+            .create();
+        let compendium = create_compendium();
+        let monster: MonsterEntry =
+            serde_json::from_str::<ApiResponse<MonsterEntry>>(&silver_moblin_data().replace(
+                "https://botw-compendium.herokuapp.com/api/v2/",
+                &format!("{}/", server_url()),
+            ))
+            .unwrap()
+            .data;
+        assert_eq!(
+            vec![137, 80, 78, 71],
+            compendium.download_entry_image(&monster).unwrap()
+        );
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_download_missing_image() {
+        let mock = create_missing_data_mock("/entry/missing/image");
+        let compendium = create_compendium();
+        assert!(matches!(
+            compendium.download_image("entry/missing/image"),
+            Err(CompendiumError::NoDataFound(_))
+        ));
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_save_image() {
+        let mock = mock("GET", "/entry/silver_moblin/image")
+            .with_status(200)
+            .with_header("content-type", "image/png")
+            .with_body([137, 80, 78, 71])
+            .create();
+        let directory = std::env::temp_dir().join(format!(
+            "rusty_hyrule_compendium_save_image_{}",
+            std::process::id()
+        ));
+        fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("silver_moblin.png");
+        let compendium = create_compendium();
+        compendium
+            .save_image("entry/silver_moblin/image", &path)
+            .unwrap();
+        assert_eq!(vec![137, 80, 78, 71], fs::read(&path).unwrap());
+        assert!(!directory.join("silver_moblin.png.part").exists());
+        fs::remove_dir_all(&directory).unwrap();
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_save_image_io_error() {
+        let mock = create_successful_mock("/entry/silver_moblin/image", "image");
+        let path = std::env::temp_dir()
+            .join("rusty_hyrule_compendium_missing_directory")
+            .join("silver_moblin.png");
+        let compendium = create_compendium();
+        assert!(matches!(
+            compendium.save_image("entry/silver_moblin/image", path),
+            Err(CompendiumError::IoError(_))
+        ));
+        mock.assert()
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_compendium_client_image_dimensions() {
+        let mut png = Vec::new();
+        image::RgbImage::new(2, 3)
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+        let mock = mock("GET", "/entry/silver_moblin/image")
+            .with_status(200)
+            .with_header("content-type", "image/png")
+            .with_body(png)
+            .create();
+        let compendium = create_compendium();
+        let image_url = format!("{}/entry/silver_moblin/image", server_url());
+        assert_eq!((2, 3), compendium.image_dimensions(&image_url).unwrap());
+        mock.assert()
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_compendium_client_image_dimensions_decode_error() {
+        let mock = mock("GET", "/entry/broken/image")
+            .with_status(200)
+            .with_body("not an image")
+            .create();
+        let compendium = create_compendium();
+        assert!(matches!(
+            compendium.image_dimensions("entry/broken/image"),
+            Err(CompendiumError::ImageDecodeError(_))
+        ));
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_entry_rank() {
+        let mock = create_successful_mock("/all", all_entries_data()).expect(5);
+        let compendium = create_compendium();
+        assert_eq!(
+            (2, 2),
+            compendium.entry_rank(EntryIdentifier::Id(112)).unwrap()
+        );
+        assert_eq!(
+            (2, 2),
+            compendium
+                .entry_rank(EntryIdentifier::Name("silver_moblin"))
+                .unwrap()
+        );
+        assert_eq!(
+            (1, 2),
+            compendium
+                .entry_rank(EntryIdentifier::Name("Bokoblin"))
+                .unwrap()
+        );
+        assert!(matches!(
+            compendium.entry_rank(EntryIdentifier::Id(1)),
+            Err(CompendiumError::NoDataFound(path)) if path == "/entry/1"
+        ));
+        assert!(matches!(
+            compendium.entry_rank(EntryIdentifier::Name("Black Moblin")),
+            Err(CompendiumError::NoDataFound(path)) if path == "/entry/black_moblin"
+        ));
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_master_mode_category() {
+        let mock =
+            create_successful_mock("/master_mode/category/monsters", monster_category_data());
+        let compendium = create_compendium();
+        match compendium
+            .master_mode_category(CompendiumCategory::Monster)
+            .unwrap()
+        {
+            CategoryResult::Monsters(monsters) => assert_eq!(112, monsters[0].id()),
+            _ => panic!("expected monsters"),
+        }
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_dataset_drift() {
+        let all_mock = create_successful_mock("/all", all_entries_data());
+        let mirror_mock = create_successful_mock(
+            "/mirror/all",
+            r#"{"data":{"creatures":{"food":[],"non_food":[]},"equipment":[],"materials":[],"monsters":[],"treasure":[]}}"#,
+        );
+        let compendium = create_compendium();
+        let mirror = CompendiumClient::new(&format!("{}/mirror/", server_url())).unwrap();
+        let drift = compendium.dataset_drift(&mirror).unwrap();
+        assert!(drift.has_drifted());
+        assert_eq!(
+            2,
+            drift.categories()[&CompendiumCategory::Monster].removed()
+        );
+        all_mock.assert();
+        mirror_mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_category_counts() {
+        let mock = create_successful_mock("/all", all_entries_data());
+        let counts = create_compendium().category_counts().unwrap();
+        assert_eq!(2, counts.count(CompendiumCategory::Monster));
+        assert_eq!(0, counts.count(CompendiumCategory::Treasure));
+        assert_eq!(2, counts.total());
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_search() {
+        let mock = create_successful_mock("/all", all_entries_data());
+        let names: Vec<String> = create_compendium()
+            .search("Bokoblin")
+            .unwrap()
+            .iter()
+            .map(|entry| entry.name().to_string())
+            .collect();
+        assert!(names.contains(&"bokoblin".to_string()));
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_find_by_name_fuzzy() {
+        let mock = create_successful_mock("/all", all_entries_data());
+        let compendium = create_compendium();
+        let names: Vec<String> = compendium
+            .find_by_name_fuzzy("bokoblen", 1)
+            .unwrap()
+            .iter()
+            .map(|entry| entry.name().to_string())
+            .collect();
+        assert_eq!(vec!["bokoblin"], names);
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_category_vs_all_discrepancies() {
+        let category_mock = create_successful_mock("/category/monsters", monster_category_data());
+        let all_mock = create_successful_mock("/all", all_entries_data());
+        let compendium = create_compendium();
+        let discrepancies = compendium
+            .category_vs_all_discrepancies(CompendiumCategory::Monster)
+            .unwrap();
+        assert_eq!(vec![104], discrepancies);
+        category_mock.assert();
+        all_mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_missing_monster_response() {
+        let mock = create_missing_data_mock("/entry/example_monster");
+        let compendium = create_compendium();
+        let identifier = EntryIdentifier::Name("example_monster");
+        assert!(matches!(
+            compendium.entry(identifier),
+            Err(CompendiumError::NoDataFound(path)) if path == "/entry/example_monster"
+        ));
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_forbidden_response() {
+        let mock = mock("GET", "/entry/silver_moblin")
+            .with_status(403)
+            .create();
+        let compendium = create_compendium();
+        let error = compendium
+            .entry(EntryIdentifier::Name("silver moblin"))
+            .unwrap_err();
+        assert_eq!(
+            "The request for '/entry/silver_moblin' was rejected with status 403",
+            error.to_string()
+        );
+        assert!(matches!(
+            error,
+            CompendiumError::ClientError { status: 403, .. }
+        ));
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_internal_server_error_response() {
+        let mock = create_server_error_data_mock("/entry/silver_moblin", silver_moblin_data());
+        let compendium = create_compendium();
+        let identifier = EntryIdentifier::Name("silver_moblin");
+        assert!(compendium.entry(identifier).is_err());
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_malformed_data_is_not_treated_as_missing() {
+        let mock =
+            create_successful_mock("/entry/silver_moblin", r#"{"data":{"id":"not a number"}}"#);
+        let compendium = create_compendium();
+        let identifier = EntryIdentifier::Name("silver_moblin");
+        assert!(matches!(
+            compendium.entry(identifier),
+            Err(CompendiumError::DeserializationError(_))
+        ));
+        mock.assert()
+    }
+
+    #[test]
+    fn test_compendium_client_unexpected_server_response() {
+        let mock = create_successful_mock("/entry/silver_moblin", missing_data_response());
+        let compendium = create_compendium();
+        let identifier = EntryIdentifier::Name("silver_moblin");
+        assert!(matches!(
+            compendium.entry(identifier),
+            Err(CompendiumError::NoDataFound(path)) if path == "/entry/silver_moblin"
+        ));
         mock.assert()
     }
 }
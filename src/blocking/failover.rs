@@ -0,0 +1,207 @@
+use super::compendium::{CompendiumApiClient, CompendiumClient, CompendiumSealed};
+use crate::domain::inputs::{CompendiumCategory, EntryIdentifier};
+#[cfg(feature = "creatures")]
+use crate::domain::models::CreatureEntry;
+#[cfg(feature = "equipment")]
+use crate::domain::models::EquipmentEntry;
+#[cfg(feature = "materials")]
+use crate::domain::models::MaterialEntry;
+#[cfg(feature = "monsters")]
+use crate::domain::models::MonsterEntry;
+#[cfg(feature = "treasure")]
+use crate::domain::models::TreasureEntry;
+use crate::domain::responses::{AllStandardEntries, CategoryResult, EntryResponse};
+use crate::error::CompendiumError;
+use crate::result::Result;
+use reqwest::Url;
+use std::sync::Mutex;
+
+/// A compendium client that requests data from a primary host, failing over to each mirror in turn when a host can't be reached.
+/// Only a [RequestError](crate::CompendiumError::RequestError), [ConnectionError](crate::CompendiumError::ConnectionError),
+/// [Timeout](crate::CompendiumError::Timeout) or [ServerError](crate::CompendiumError::ServerError) causes a failover,
+/// any other error (e.g. [NoDataFound](crate::CompendiumError::NoDataFound)) is returned straight away as every host would respond the same.
+#[derive(Debug)]
+pub struct FailoverCompendiumClient {
+    clients: Vec<CompendiumClient>,
+    last_host: Mutex<Option<Url>>,
+}
+
+impl FailoverCompendiumClient {
+    /// Initialise a client for the primary host and mirrors, which are tried in the order given
+    pub fn new(primary: &str, mirrors: &[&str]) -> Result<Self> {
+        let clients = std::iter::once(primary)
+            .chain(mirrors.iter().copied())
+            .map(CompendiumClient::new)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(FailoverCompendiumClient {
+            clients,
+            last_host: Mutex::new(None),
+        })
+    }
+
+    /// The base url of the host that served the last request, or `None` when no host has responded yet
+    pub fn last_host(&self) -> Option<Url> {
+        self.last_host
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    fn with_failover<T>(&self, request: impl Fn(&CompendiumClient) -> Result<T>) -> Result<T> {
+        let mut last_error = None;
+        for client in &self.clients {
+            match request(client) {
+                Err(
+                    error @ (CompendiumError::RequestError(_)
+                    | CompendiumError::ConnectionError(_)
+                    | CompendiumError::Timeout(_)
+                    | CompendiumError::ServerError),
+                ) => {
+                    last_error = Some(error);
+                }
+                result => {
+                    *self
+                        .last_host
+                        .lock()
+                        .unwrap_or_else(|poisoned| poisoned.into_inner()) =
+                        Some(client.base_url().clone());
+                    return result;
+                }
+            }
+        }
+        Err(last_error.expect("there is always at least the primary host"))
+    }
+}
+
+impl CompendiumApiClient for FailoverCompendiumClient {
+    fn entry(&self, identifier: EntryIdentifier) -> Result<EntryResponse> {
+        self.with_failover(|client| client.entry(identifier.clone()))
+    }
+
+    #[cfg(feature = "monsters")]
+    fn monster(&self, identifier: EntryIdentifier) -> Result<MonsterEntry> {
+        self.with_failover(|client| client.monster(identifier.clone()))
+    }
+
+    #[cfg(feature = "monsters")]
+    fn master_mode_monster(&self, identifier: EntryIdentifier) -> Result<MonsterEntry> {
+        self.with_failover(|client| client.master_mode_monster(identifier.clone()))
+    }
+
+    #[cfg(feature = "treasure")]
+    fn treasure(&self, identifier: EntryIdentifier) -> Result<TreasureEntry> {
+        self.with_failover(|client| client.treasure(identifier.clone()))
+    }
+
+    #[cfg(feature = "creatures")]
+    fn creature(&self, identifier: EntryIdentifier) -> Result<CreatureEntry> {
+        self.with_failover(|client| client.creature(identifier.clone()))
+    }
+
+    #[cfg(feature = "materials")]
+    fn material(&self, identifier: EntryIdentifier) -> Result<MaterialEntry> {
+        self.with_failover(|client| client.material(identifier.clone()))
+    }
+
+    #[cfg(feature = "equipment")]
+    fn equipment(&self, identifier: EntryIdentifier) -> Result<EquipmentEntry> {
+        self.with_failover(|client| client.equipment(identifier.clone()))
+    }
+
+    fn category(&self, category: CompendiumCategory) -> Result<CategoryResult> {
+        self.with_failover(|client| client.category(category))
+    }
+
+    fn master_mode_category(&self, category: CompendiumCategory) -> Result<CategoryResult> {
+        self.with_failover(|client| client.master_mode_category(category))
+    }
+
+    fn all_entries(&self) -> Result<AllStandardEntries> {
+        self.with_failover(CompendiumClient::all_entries)
+    }
+
+    #[cfg(feature = "monsters")]
+    fn all_master_mode_entries(&self) -> Result<Vec<MonsterEntry>> {
+        self.with_failover(CompendiumClient::all_master_mode_entries)
+    }
+}
+
+impl CompendiumSealed for FailoverCompendiumClient {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::CompendiumEntry;
+    use mockito::{mock, server_url, Mock};
+
+    fn silver_moblin_data<'a>() -> &'a str {
+        r#"{"data":{"category":"monsters","common_locations":null,"description":"The strongest of all Moblins.","drops":["moblin horn"],"id":112,"image":"https://botw-compendium.herokuapp.com/api/v2/entry/silver_moblin/image","name":"silver moblin"}}"#
+    }
+
+    fn create_mock(path: &str, status: usize, body: &str) -> Mock {
+        mock("GET", path)
+            .with_status(status)
+            .with_header("content-type", "application/json")
+            .with_body(body)
+            .create()
+    }
+
+    fn unreachable_host() -> String {
+        let unused_address = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        format!("http://{}/", unused_address)
+    }
+
+    #[test]
+    fn test_failover_client_uses_mirror_when_primary_unreachable() {
+        let mock = create_mock(
+            "/failover-mirror/entry/silver_moblin",
+            200,
+            silver_moblin_data(),
+        );
+        let mirror = format!("{}/failover-mirror/", server_url());
+        let client = FailoverCompendiumClient::new(&unreachable_host(), &[&mirror]).unwrap();
+        assert!(client.last_host().is_none());
+        let entry = client
+            .entry(EntryIdentifier::Name("silver moblin"))
+            .unwrap();
+        assert_eq!(112, entry.id());
+        assert_eq!(Some(Url::parse(&mirror).unwrap()), client.last_host());
+        mock.assert()
+    }
+
+    #[test]
+    fn test_failover_client_uses_mirror_on_server_error() {
+        let primary_mock = create_mock("/failover-primary/entry/112", 500, "");
+        let mirror_mock = create_mock("/failover-mirror/entry/112", 200, silver_moblin_data());
+        let primary = format!("{}/failover-primary/", server_url());
+        let mirror = format!("{}/failover-mirror/", server_url());
+        let client = FailoverCompendiumClient::new(&primary, &[&mirror]).unwrap();
+        let monster = client.monster(EntryIdentifier::Id(112)).unwrap();
+        assert_eq!(112, monster.id());
+        primary_mock.assert();
+        mirror_mock.assert()
+    }
+
+    #[test]
+    fn test_failover_client_does_not_fail_over_when_no_data_found() {
+        let mock = create_mock("/failover-primary/entry/missing", 404, "");
+        let primary = format!("{}/failover-primary/", server_url());
+        let client = FailoverCompendiumClient::new(&primary, &[&unreachable_host()]).unwrap();
+        let result = client.entry(EntryIdentifier::Name("missing"));
+        assert!(matches!(result, Err(CompendiumError::NoDataFound(_))));
+        assert_eq!(Some(Url::parse(&primary).unwrap()), client.last_host());
+        mock.assert()
+    }
+
+    #[test]
+    fn test_failover_client_returns_last_error_when_all_hosts_fail() {
+        let client =
+            FailoverCompendiumClient::new(&unreachable_host(), &[&unreachable_host()]).unwrap();
+        let result = client.entry(EntryIdentifier::Id(112));
+        assert!(matches!(result, Err(CompendiumError::ConnectionError(_))));
+        assert!(client.last_host().is_none());
+    }
+}
@@ -0,0 +1,51 @@
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// A token bucket holding a single token, refilled at a fixed rate, spacing requests evenly so that no more than the
+/// configured number are made each second. Clients cloned from one another share their limiter, and so their rate.
+#[derive(Debug)]
+pub(super) struct RateLimiter {
+    interval: Duration,
+    next_token: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    pub(super) fn new(requests_per_second: u32) -> Self {
+        RateLimiter {
+            interval: Duration::from_secs(1) / requests_per_second.max(1),
+            next_token: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Block until a token is available, taking it
+    pub(super) fn acquire(&self) {
+        let now = Instant::now();
+        let token_at = {
+            let mut next_token = self
+                .next_token
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            let token_at = (*next_token).max(now);
+            *next_token = token_at + self.interval;
+            token_at
+        };
+        thread::sleep(token_at - now);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limiter_spaces_tokens() {
+        let limiter = RateLimiter::new(20);
+        let started = Instant::now();
+        for _ in 0..5 {
+            limiter.acquire();
+        }
+        // The first token is available straight away, the other four are 50ms apart
+        assert!(started.elapsed() >= Duration::from_millis(200));
+    }
+}
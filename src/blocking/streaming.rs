@@ -0,0 +1,238 @@
+use crate::api::NO_RESULTS_MESSAGE;
+use crate::domain::inputs::CompendiumCategory;
+use crate::domain::models::CompendiumEntry;
+use crate::domain::responses::EntryResponse;
+use serde::de::value::MapAccessDeserializer;
+use serde::de::{
+    Deserialize, DeserializeSeed, Deserializer, Error, IgnoredAny, MapAccess, SeqAccess, Visitor,
+};
+use std::collections::HashSet;
+use std::fmt;
+
+/// Passes each entry to the callback as it's deserialized, checking for duplicate ids within each category when asked to
+pub(super) struct EntrySink<F> {
+    callback: F,
+    seen_ids: Option<HashSet<(CompendiumCategory, i32)>>,
+    duplicate_id: Option<i32>,
+}
+
+impl<F: FnMut(EntryResponse)> EntrySink<F> {
+    pub(super) fn new(callback: F, detect_duplicate_ids: bool) -> Self {
+        EntrySink {
+            callback,
+            seen_ids: detect_duplicate_ids.then(HashSet::new),
+            duplicate_id: None,
+        }
+    }
+
+    /// The duplicate id that stopped deserialization, if that's why it failed
+    pub(super) fn duplicate_id(&self) -> Option<i32> {
+        self.duplicate_id
+    }
+
+    fn accept<E: Error>(
+        &mut self,
+        category: CompendiumCategory,
+        entry: EntryResponse,
+    ) -> Result<(), E> {
+        if let Some(seen_ids) = &mut self.seen_ids {
+            if !seen_ids.insert((category, entry.id())) {
+                self.duplicate_id = Some(entry.id());
+                return Err(E::custom(format!("duplicate id {}", entry.id())));
+            }
+        }
+        (self.callback)(entry);
+        Ok(())
+    }
+}
+
+/// What's left of a page of all entries once its entries have been passed on
+pub(super) struct Page {
+    /// The link to the next page, if there is one
+    pub(super) next: Option<String>,
+    /// Whether the API responded without any results, i.e. `{"data":{},"message":"no results"}`
+    pub(super) no_results: bool,
+}
+
+/// A page of all entries in the API's `{"data": ..., "next": ...}` envelope
+pub(super) struct PageSeed<'s, F>(pub(super) &'s mut EntrySink<F>);
+
+impl<'de, F: FnMut(EntryResponse)> DeserializeSeed<'de> for PageSeed<'_, F> {
+    type Value = Page;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, F: FnMut(EntryResponse)> Visitor<'de> for PageSeed<'_, F> {
+    type Value = Page;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a response with all entries in its data")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let PageSeed(sink) = self;
+        let mut has_categories = None;
+        let mut next = None;
+        let mut message: Option<String> = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "data" => has_categories = Some(map.next_value_seed(AllEntriesSeed(&mut *sink))?),
+                "next" => next = map.next_value()?,
+                "message" => message = map.next_value()?,
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        let has_categories = has_categories.ok_or_else(|| A::Error::missing_field("data"))?;
+        Ok(Page {
+            next,
+            no_results: !has_categories || message.as_deref() == Some(NO_RESULTS_MESSAGE),
+        })
+    }
+}
+
+/// All entries keyed by category, in the shape of [AllStandardEntries](crate::domain::responses::AllStandardEntries).
+/// As with it, categories whose features are disabled are ignored. Deserializes to whether there were any categories at all.
+struct AllEntriesSeed<'s, F>(&'s mut EntrySink<F>);
+
+impl<'de, F: FnMut(EntryResponse)> DeserializeSeed<'de> for AllEntriesSeed<'_, F> {
+    type Value = bool;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de, F: FnMut(EntryResponse)> Visitor<'de> for AllEntriesSeed<'_, F> {
+    type Value = bool;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map of categories to their entries")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let AllEntriesSeed(sink) = self;
+        let mut has_categories = false;
+        while let Some(key) = map.next_key::<String>()? {
+            has_categories = true;
+            match key.as_str() {
+                #[cfg(feature = "creatures")]
+                "creatures" => map.next_value_seed(CreaturesSeed(&mut *sink))?,
+                #[cfg(feature = "equipment")]
+                "equipment" => map.next_value_seed(EntriesSeed {
+                    sink: &mut *sink,
+                    category: CompendiumCategory::Equipment,
+                    wrap: EntryResponse::Equipment,
+                })?,
+                #[cfg(feature = "materials")]
+                "materials" => map.next_value_seed(EntriesSeed {
+                    sink: &mut *sink,
+                    category: CompendiumCategory::Material,
+                    wrap: EntryResponse::Material,
+                })?,
+                #[cfg(feature = "monsters")]
+                "monsters" => map.next_value_seed(EntriesSeed {
+                    sink: &mut *sink,
+                    category: CompendiumCategory::Monster,
+                    wrap: EntryResponse::Monster,
+                })?,
+                #[cfg(feature = "treasure")]
+                "treasure" => map.next_value_seed(EntriesSeed {
+                    sink: &mut *sink,
+                    category: CompendiumCategory::Treasure,
+                    wrap: EntryResponse::Treasure,
+                })?,
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        Ok(has_categories)
+    }
+}
+
+/// The food and non-food creature entries, in the shape of [AllCreatureEntries](crate::domain::responses::AllCreatureEntries)
+#[cfg(feature = "creatures")]
+struct CreaturesSeed<'s, F>(&'s mut EntrySink<F>);
+
+#[cfg(feature = "creatures")]
+impl<'de, F: FnMut(EntryResponse)> DeserializeSeed<'de> for CreaturesSeed<'_, F> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+#[cfg(feature = "creatures")]
+impl<'de, F: FnMut(EntryResponse)> Visitor<'de> for CreaturesSeed<'_, F> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("the food and non-food creatures")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let CreaturesSeed(sink) = self;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "food" | "non_food" => map.next_value_seed(EntriesSeed {
+                    sink: &mut *sink,
+                    category: CompendiumCategory::Creature,
+                    wrap: EntryResponse::Creature,
+                })?,
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The entries of a category, which the API may return as a single entry rather than a list when there's only one
+struct EntriesSeed<'s, T, F> {
+    sink: &'s mut EntrySink<F>,
+    category: CompendiumCategory,
+    wrap: fn(T) -> EntryResponse,
+}
+
+impl<'de, T, F> DeserializeSeed<'de> for EntriesSeed<'_, T, F>
+where
+    T: Deserialize<'de>,
+    F: FnMut(EntryResponse),
+{
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de, T, F> Visitor<'de> for EntriesSeed<'_, T, F>
+where
+    T: Deserialize<'de>,
+    F: FnMut(EntryResponse),
+{
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an entry or a list of entries")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        while let Some(entry) = seq.next_element::<T>()? {
+            self.sink.accept(self.category, (self.wrap)(entry))?;
+        }
+        Ok(())
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+        let entry = T::deserialize(MapAccessDeserializer::new(map))?;
+        self.sink.accept(self.category, (self.wrap)(entry))
+    }
+}
@@ -0,0 +1,45 @@
+//! Pluggable caching of entries requested by a compendium client
+use crate::domain::responses::EntryResponse;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Mutex;
+
+/// A store of previously requested entries that a client checks before making a request.
+///
+/// Entries are keyed by the path of the entry relative to the client's base url, e.g. `entry/silver_moblin` or `entry/112`.
+/// As the key is derived from the identifier used, an entry requested by id and by name is cached under two separate keys.
+pub trait CacheBackend: Debug + Send + Sync {
+    /// Get the cached entry for the key, if there is one
+    fn get(&self, key: &str) -> Option<EntryResponse>;
+
+    /// Store the entry under the key
+    fn put(&self, key: &str, value: EntryResponse);
+}
+
+/// A [CacheBackend] that holds entries in memory for the lifetime of the cache
+#[derive(Debug, Default)]
+pub struct InMemoryCache {
+    entries: Mutex<HashMap<String, EntryResponse>>,
+}
+
+impl InMemoryCache {
+    /// Create an empty in-memory cache
+    pub fn new() -> Self {
+        InMemoryCache::default()
+    }
+}
+
+impl CacheBackend for InMemoryCache {
+    fn get(&self, key: &str) -> Option<EntryResponse> {
+        self.entries
+            .lock()
+            .ok()
+            .and_then(|entries| entries.get(key).cloned())
+    }
+
+    fn put(&self, key: &str, value: EntryResponse) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.insert(key.to_string(), value);
+        }
+    }
+}
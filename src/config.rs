@@ -0,0 +1,145 @@
+//! The configuration a compendium client was built with
+use crate::domain::inputs::GameVersion;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// The url of v2 of the Hyrule Compendium API
+pub(crate) const DEFAULT_BASE_URL: &str = "https://botw-compendium.herokuapp.com/api/v2/";
+/// The url of v3 of the Hyrule Compendium API, which also provides Tears of the Kingdom
+pub(crate) const V3_BASE_URL: &str = "https://botw-compendium.herokuapp.com/api/v3/compendium/";
+/// The User-Agent header sent with every request unless another is configured, identifying this crate and its version
+pub(crate) const DEFAULT_USER_AGENT: &str =
+    concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+/// The url of the web compendium that presents the API's data as HTML pages
+#[cfg(feature = "scraping")]
+const DEFAULT_WEB_URL: &str = "https://botw-compendium.herokuapp.com/";
+/// How long to wait for the API's host to wake up before retrying a request
+const DEFAULT_WAKE_UP_DELAY: Duration = Duration::from_secs(5);
+/// How long to wait before the first retry of a failed request, doubling for each retry after
+const DEFAULT_BASE_BACKOFF: Duration = Duration::from_millis(500);
+/// The longest wait before retrying a failed request, however many retries have been made
+pub(crate) const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// The settings used to configure a compendium client.
+/// This only records the configuration, it doesn't expose the underlying HTTP client.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ClientConfig {
+    pub(crate) base_url: String,
+    pub(crate) game_version: GameVersion,
+    pub(crate) validate_category_types: bool,
+    pub(crate) detect_duplicate_ids: bool,
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) connect_timeout: Option<Duration>,
+    pub(crate) bulk_timeout: Option<Duration>,
+    pub(crate) wake_up_delay: Duration,
+    pub(crate) user_agent: String,
+    pub(crate) retries: u32,
+    pub(crate) base_backoff: Duration,
+    pub(crate) compression: bool,
+    pub(crate) proxy: Option<String>,
+    pub(crate) requests_per_second: Option<u32>,
+    #[cfg(feature = "scraping")]
+    pub(crate) web_url: String,
+}
+
+impl Default for ClientConfig {
+    fn default() -> ClientConfig {
+        ClientConfig {
+            base_url: DEFAULT_BASE_URL.to_string(),
+            game_version: GameVersion::default(),
+            validate_category_types: false,
+            detect_duplicate_ids: false,
+            timeout: None,
+            connect_timeout: None,
+            bulk_timeout: None,
+            wake_up_delay: DEFAULT_WAKE_UP_DELAY,
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            retries: 0,
+            base_backoff: DEFAULT_BASE_BACKOFF,
+            compression: true,
+            proxy: None,
+            requests_per_second: None,
+            #[cfg(feature = "scraping")]
+            web_url: DEFAULT_WEB_URL.to_string(),
+        }
+    }
+}
+
+impl ClientConfig {
+    /// Get the base url of the API the client requests data from
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Get the game whose compendium the client requests data from
+    pub fn game_version(&self) -> GameVersion {
+        self.game_version
+    }
+
+    /// Get whether entries returned while requesting a category are checked against the requested category
+    pub fn validate_category_types(&self) -> bool {
+        self.validate_category_types
+    }
+
+    /// Get whether entries returned while requesting a category or all entries are checked for duplicate ids
+    pub fn detect_duplicate_ids(&self) -> bool {
+        self.detect_duplicate_ids
+    }
+
+    /// Get the timeout applied to every request, `None` when the HTTP client's default is used
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Get the timeout applied to establishing a connection, `None` when the HTTP client's default is used
+    pub fn connect_timeout(&self) -> Option<Duration> {
+        self.connect_timeout
+    }
+
+    /// Get the timeout applied to requests for all entries, `None` when the request timeout is used
+    pub fn bulk_timeout(&self) -> Option<Duration> {
+        self.bulk_timeout
+    }
+
+    /// Get how long to wait for the API's host to wake up before retrying a request that couldn't connect or timed out
+    pub fn wake_up_delay(&self) -> Duration {
+        self.wake_up_delay
+    }
+
+    /// Get the User-Agent header sent with every request
+    pub fn user_agent(&self) -> &str {
+        &self.user_agent
+    }
+
+    /// Get how many times a request that failed with a server or connection error is retried
+    pub fn retries(&self) -> u32 {
+        self.retries
+    }
+
+    /// Get how long to wait before the first retry of a failed request, which doubles for each retry after
+    pub fn base_backoff(&self) -> Duration {
+        self.base_backoff
+    }
+
+    /// Get whether gzip and deflate compressed responses are requested and transparently decompressed
+    pub fn compression(&self) -> bool {
+        self.compression
+    }
+
+    /// Get the url of the proxy every request is sent through, if any.
+    /// Only its scheme, host and port are kept, any credentials it was configured with are removed.
+    pub fn proxy(&self) -> Option<&str> {
+        self.proxy.as_deref()
+    }
+
+    /// Get the maximum number of requests made each second, `None` when requests aren't limited
+    pub fn requests_per_second(&self) -> Option<u32> {
+        self.requests_per_second
+    }
+
+    /// Get the url of the web compendium whose pages are read when falling back from the API
+    #[cfg(feature = "scraping")]
+    pub fn web_url(&self) -> &str {
+        &self.web_url
+    }
+}
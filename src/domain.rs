@@ -3,5 +3,7 @@
 /// The available inputs in requesting data
 pub mod inputs;
 pub mod models;
+/// Queries across collections of entries
+pub mod query;
 /// The representation of response types
 pub mod responses;
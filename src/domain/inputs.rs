@@ -1,27 +1,193 @@
+use crate::config::{DEFAULT_BASE_URL, V3_BASE_URL};
+use crate::domain::models::normalize_for_comparison;
+use crate::error::CompendiumError;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
 /// An enum representing the ways of requesting an entry
-#[derive(Debug, Clone, Copy)]
+///
+/// Identifiers can also be created from an id, a borrowed name or an owned name with `into()`
+/// ```rust
+/// use rusty_hyrule_compendium::domain::inputs::EntryIdentifier;
+///
+/// let by_id: EntryIdentifier = 112.into();
+/// let by_name: EntryIdentifier = "silver moblin".into();
+/// let by_owned_name: EntryIdentifier = format!("{} moblin", "silver").into();
+/// ```
+///
+/// Identifiers aren't `Copy`, as an [OwnedName](EntryIdentifier::OwnedName) owns its `String`, so clone an identifier to use it for more than one request.
+/// Cloning an [Id](EntryIdentifier::Id) or [Name](EntryIdentifier::Name) is as cheap as copying it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum EntryIdentifier<'a> {
     /// The entry's id (e.g. 1 for horse) in the compendium
     Id(i32),
     /// The entry's name (e.g. silver moblin) in the compendium
     Name(&'a str),
+    /// The entry's name, owned so it doesn't need to outlive the identifier, e.g. when built at runtime
+    OwnedName(String),
+}
+
+impl EntryIdentifier<'_> {
+    /// An identifier for the entry with the given owned name
+    pub fn name_owned(name: String) -> EntryIdentifier<'static> {
+        EntryIdentifier::OwnedName(name)
+    }
+}
+
+impl From<i32> for EntryIdentifier<'_> {
+    fn from(id: i32) -> Self {
+        EntryIdentifier::Id(id)
+    }
+}
+
+impl<'a> From<&'a str> for EntryIdentifier<'a> {
+    fn from(name: &'a str) -> Self {
+        EntryIdentifier::Name(name)
+    }
+}
+
+impl<'a> From<&'a String> for EntryIdentifier<'a> {
+    fn from(name: &'a String) -> Self {
+        EntryIdentifier::Name(name)
+    }
+}
+
+impl From<String> for EntryIdentifier<'_> {
+    fn from(name: String) -> Self {
+        EntryIdentifier::OwnedName(name)
+    }
+}
+
+/// Normalize an entry's name the way the client does when requesting it, e.g. to build cache keys matching the client's requests.
+/// The name is trimmed, lowercased and each run of whitespace is replaced with a single underscore, so " Silver  Moblin" becomes "silver_moblin".
+/// Existing underscores and punctuation, such as apostrophes and hyphens, are kept as they are since the API's names include them.
+/// ```rust
+/// use rusty_hyrule_compendium::domain::inputs::normalize_name;
+///
+/// assert_eq!("white-maned_lynel", normalize_name("White-maned Lynel"));
+/// ```
+pub fn normalize_name(name: &str) -> String {
+    normalize_for_comparison(name).replace(' ', "_")
 }
 
 /// An enum representing all the compendium category types
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
 pub enum CompendiumCategory {
     /// The treasure category in the compendium
+    #[cfg(feature = "treasure")]
     Treasure,
     /// The creature category in the compendium
+    #[cfg(feature = "creatures")]
     Creature,
     /// The monster category in the compendium
+    #[cfg(feature = "monsters")]
     Monster,
     /// The material category in the compendium
+    #[cfg(feature = "materials")]
     Material,
     /// The equipment category in the compendium
+    #[cfg(feature = "equipment")]
     Equipment,
 }
 
+impl CompendiumCategory {
+    /// Every category in the compendium (whose feature is enabled)
+    pub const ALL: &'static [CompendiumCategory] = &[
+        #[cfg(feature = "creatures")]
+        CompendiumCategory::Creature,
+        #[cfg(feature = "equipment")]
+        CompendiumCategory::Equipment,
+        #[cfg(feature = "materials")]
+        CompendiumCategory::Material,
+        #[cfg(feature = "monsters")]
+        CompendiumCategory::Monster,
+        #[cfg(feature = "treasure")]
+        CompendiumCategory::Treasure,
+    ];
+
+    /// The category's name as used by the API (e.g. "monsters" or "treasure")
+    pub(crate) fn api_name(&self) -> &'static str {
+        match self {
+            #[cfg(feature = "creatures")]
+            CompendiumCategory::Creature => "creatures",
+            #[cfg(feature = "monsters")]
+            CompendiumCategory::Monster => "monsters",
+            #[cfg(feature = "materials")]
+            CompendiumCategory::Material => "materials",
+            #[cfg(feature = "treasure")]
+            CompendiumCategory::Treasure => "treasure",
+            #[cfg(feature = "equipment")]
+            CompendiumCategory::Equipment => "equipment",
+        }
+    }
+}
+
+/// Displays the category's name as used by the API, e.g. "monsters"
+impl fmt::Display for CompendiumCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.api_name())
+    }
+}
+
+/// Parses a category from its singular or plural name, ignoring case and surrounding whitespace (e.g. "Monster" or "monsters")
+impl FromStr for CompendiumCategory {
+    type Err = CompendiumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            #[cfg(feature = "creatures")]
+            "creature" | "creatures" => Ok(CompendiumCategory::Creature),
+            #[cfg(feature = "equipment")]
+            "equipment" | "equipments" => Ok(CompendiumCategory::Equipment),
+            #[cfg(feature = "materials")]
+            "material" | "materials" => Ok(CompendiumCategory::Material),
+            #[cfg(feature = "monsters")]
+            "monster" | "monsters" => Ok(CompendiumCategory::Monster),
+            #[cfg(feature = "treasure")]
+            "treasure" | "treasures" => Ok(CompendiumCategory::Treasure),
+            _ => Err(CompendiumError::UnknownCategory(s.to_string())),
+        }
+    }
+}
+
+/// How a method that makes several requests behaves when some of them fail
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
+pub enum FailurePolicy {
+    /// Stop at the first failed request and return its error
+    AbortOnFirst,
+    /// Carry on after a failed request, returning whatever data was retrieved along with every error
+    CollectErrors,
+}
+
+/// An enum representing the games whose compendium the API provides
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy, Default, Serialize, Deserialize)]
+pub enum GameVersion {
+    /// Breath of the Wild, provided by v2 of the API
+    #[default]
+    BreathOfTheWild,
+    /// Tears of the Kingdom, provided by v3 of the API
+    TearsOfTheKingdom,
+}
+
+impl GameVersion {
+    /// The url of the version of the API that provides the game's compendium
+    pub fn base_url(&self) -> &'static str {
+        match self {
+            GameVersion::BreathOfTheWild => DEFAULT_BASE_URL,
+            GameVersion::TearsOfTheKingdom => V3_BASE_URL,
+        }
+    }
+
+    /// The value of the `game` query parameter selecting the game, which v2 of the API doesn't need
+    pub(crate) fn query_value(&self) -> Option<&'static str> {
+        match self {
+            GameVersion::BreathOfTheWild => None,
+            GameVersion::TearsOfTheKingdom => Some("totk"),
+        }
+    }
+}
+
 /// An enum representing the two game modes available in botw, standard and master mode
 #[derive(Debug, Clone, PartialEq, Eq, Copy)]
 pub enum GameMode {
@@ -30,3 +196,135 @@ pub enum GameMode {
     /// Master mode
     MasterMode,
 }
+
+/// A gameplay situation that a cooking effect helps with, see [effect](Situation::effect) for the effect each maps to
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
+pub enum Situation {
+    /// Somewhere cold, helped by "cold resistance"
+    Cold,
+    /// Somewhere hot, helped by "heat resistance"
+    Heat,
+    /// Somewhere that sets things alight (e.g. Death Mountain), helped by "fire resistance"
+    Fire,
+    /// Facing electric attacks, helped by "electric resistance"
+    Electric,
+    /// Sneaking up on something, helped by "stealth up"
+    Stealth,
+    /// Needing to move faster, helped by "speed up"
+    Speed,
+    /// Needing to deal more damage, helped by "attack up"
+    Attack,
+    /// Needing to take less damage, helped by "defense up"
+    Defense,
+    /// Running out of stamina, helped by "stamina recovery"
+    Stamina,
+    /// Needing more stamina than the wheel holds, helped by "extra stamina"
+    ExtraStamina,
+    /// Needing more hearts than are available, helped by "extra hearts"
+    ExtraHearts,
+}
+
+impl Situation {
+    /// The cooking effect, as named by the API, that helps with the situation
+    pub fn effect(&self) -> &'static str {
+        match self {
+            Situation::Cold => "cold resistance",
+            Situation::Heat => "heat resistance",
+            Situation::Fire => "fire resistance",
+            Situation::Electric => "electric resistance",
+            Situation::Stealth => "stealth up",
+            Situation::Speed => "speed up",
+            Situation::Attack => "attack up",
+            Situation::Defense => "defense up",
+            Situation::Stamina => "stamina recovery",
+            Situation::ExtraStamina => "extra stamina",
+            Situation::ExtraHearts => "extra hearts",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_name() {
+        assert_eq!("silver_moblin", normalize_name("silver moblin"));
+        assert_eq!("silver_moblin", normalize_name("Silver MOBLIN"));
+        assert_eq!("silver_moblin", normalize_name("  silver \t moblin "));
+        assert_eq!("silver_moblin", normalize_name("silver_moblin"));
+        assert_eq!(
+            "shard_of_farosh's_horn",
+            normalize_name("Shard of Farosh's Horn")
+        );
+        assert_eq!("white-maned_lynel", normalize_name("White-Maned Lynel"));
+        assert_eq!("", normalize_name(""));
+    }
+
+    #[test]
+    fn test_entry_identifier_from() {
+        assert_eq!(EntryIdentifier::Id(112), 112.into());
+        assert_eq!(
+            EntryIdentifier::Name("silver moblin"),
+            "silver moblin".into()
+        );
+        let name = String::from("silver moblin");
+        assert_eq!(EntryIdentifier::Name("silver moblin"), (&name).into());
+        assert_eq!(
+            EntryIdentifier::OwnedName(String::from("silver moblin")),
+            name.into()
+        );
+        assert_eq!(
+            EntryIdentifier::OwnedName(String::from("silver moblin")),
+            EntryIdentifier::name_owned(String::from("silver moblin"))
+        );
+    }
+
+    #[test]
+    fn test_category_from_str_accepts_singular_and_plural() {
+        for name in ["monster", "monsters", "Monster", " MONSTERS "] {
+            assert_eq!(
+                CompendiumCategory::Monster,
+                name.parse::<CompendiumCategory>().unwrap()
+            );
+        }
+        assert_eq!(
+            CompendiumCategory::Creature,
+            "creature".parse::<CompendiumCategory>().unwrap()
+        );
+        assert_eq!(
+            CompendiumCategory::Material,
+            "Materials".parse::<CompendiumCategory>().unwrap()
+        );
+        assert_eq!(
+            CompendiumCategory::Treasure,
+            "treasure".parse::<CompendiumCategory>().unwrap()
+        );
+        assert_eq!(
+            CompendiumCategory::Equipment,
+            "equipment".parse::<CompendiumCategory>().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_category_from_str_rejects_unknown_names() {
+        for name in ["", "monstr", "weapons"] {
+            match name.parse::<CompendiumCategory>() {
+                Err(CompendiumError::UnknownCategory(unknown)) => assert_eq!(name, unknown),
+                result => panic!("Expected an unknown category error, got {:?}", result),
+            }
+        }
+    }
+
+    #[test]
+    fn test_category_display_round_trips() {
+        assert_eq!("monsters", CompendiumCategory::Monster.to_string());
+        assert_eq!("treasure", CompendiumCategory::Treasure.to_string());
+        for category in CompendiumCategory::ALL {
+            assert_eq!(
+                *category,
+                category.to_string().parse::<CompendiumCategory>().unwrap()
+            );
+        }
+    }
+}
@@ -1,16 +1,43 @@
 //! Available entries from the compendium
 
 mod common_entry;
+mod compendium_entry;
+#[cfg(feature = "creatures")]
 mod creature_entry;
+mod entry_summary;
+#[cfg(feature = "equipment")]
 mod equipment_entry;
+mod has_drops;
+#[cfg(feature = "materials")]
 mod material_entry;
+#[cfg(feature = "monsters")]
 mod monster_entry;
+#[cfg(feature = "treasure")]
 mod treasure_entry;
 
 #[doc(hidden)]
 pub(crate) use common_entry::CommonEntry;
+pub use compendium_entry::CompendiumEntry;
+/// A shorter name for [CompendiumEntry], the accessors shared by every entry, for writing code generic over any entry
+/// ```rust
+/// use rusty_hyrule_compendium::domain::models::Entry;
+///
+/// fn print_entry(entry: &impl Entry) {
+///     println!("{} ({})", entry.name(), entry.id());
+/// }
+/// ```
+pub use compendium_entry::CompendiumEntry as Entry;
+pub(crate) use compendium_entry::{normalize_for_comparison, render_markdown};
+#[cfg(feature = "creatures")]
 pub use creature_entry::CreatureEntry;
-pub use equipment_entry::EquipmentEntry;
+pub use entry_summary::EntrySummary;
+#[cfg(feature = "equipment")]
+pub use equipment_entry::{EquipmentEntry, EquipmentKind};
+pub(crate) use has_drops::normalize_drop;
+pub use has_drops::HasDrops;
+#[cfg(feature = "materials")]
 pub use material_entry::MaterialEntry;
-pub use monster_entry::MonsterEntry;
+#[cfg(feature = "monsters")]
+pub use monster_entry::{DangerLevel, MonsterEntry};
+#[cfg(feature = "treasure")]
 pub use treasure_entry::TreasureEntry;
@@ -8,6 +8,8 @@ pub(crate) struct CommonEntry {
     description: String,
     common_locations: Option<Vec<String>>,
     image: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    dlc: Option<bool>,
 }
 
 impl CommonEntry {
@@ -35,4 +37,9 @@ impl CommonEntry {
     pub fn image(&self) -> &str {
         &self.image
     }
+
+    /// Get whether the entry is from downloadable content
+    pub fn dlc(&self) -> Option<bool> {
+        self.dlc
+    }
 }
@@ -0,0 +1,220 @@
+use crate::error::CompendiumError;
+use crate::result::Result;
+use reqwest::Url;
+use std::borrow::Cow;
+use std::fmt::{self, Write};
+use std::ops::RangeInclusive;
+
+/// The accessors shared by every entry in the compendium, regardless of its category
+pub trait CompendiumEntry {
+    /// Get the entry's id
+    fn id(&self) -> i32;
+
+    /// Get the entry's name
+    fn name(&self) -> &str;
+
+    /// Get the entry's description
+    fn description(&self) -> &str;
+
+    /// Get the entry's common locations
+    fn common_locations(&self) -> Option<&Vec<String>>;
+
+    /// Get the entry's image
+    fn image(&self) -> &str;
+
+    /// Get the entry's category type
+    fn category_type(&self) -> &str;
+
+    /// Get whether the entry is from downloadable content, which is `None` when the API doesn't say.
+    /// Every entry from this crate provides this, it defaults to `None` for other implementations.
+    fn dlc(&self) -> Option<bool> {
+        None
+    }
+
+    /// Whether the entry is from downloadable content, treating entries the API doesn't mark either way as from the base game
+    fn is_dlc(&self) -> bool {
+        self.dlc().unwrap_or(false)
+    }
+
+    /// Whether the entry is from downloadable content, falling back to whether its id is in one of the given ranges when the API doesn't say.
+    /// This is for data where the API doesn't mark entries, where the ranges of DLC ids are known (e.g. from the game's compendium numbering).
+    /// ```rust
+    /// use rusty_hyrule_compendium::domain::models::CompendiumEntry;
+    ///
+    /// fn is_dlc(entry: &dyn CompendiumEntry) -> bool {
+    ///     entry.is_dlc_in(&[390..=400])
+    /// }
+    /// ```
+    fn is_dlc_in(&self, dlc_ids: &[RangeInclusive<i32>]) -> bool {
+        self.dlc()
+            .unwrap_or_else(|| dlc_ids.iter().any(|ids| ids.contains(&self.id())))
+    }
+
+    /// Get the entry's name normalized for comparison: lowercased, trimmed and with runs of whitespace collapsed to a single space.
+    /// The name is only copied when it needs changing, otherwise it's borrowed as is.
+    fn name_normalized(&self) -> Cow<'_, str> {
+        normalize_for_comparison(self.name())
+    }
+
+    /// Get the entry's image as a parsed url, e.g. to validate it before requesting the image.
+    /// An image that isn't a valid absolute url, including an empty one, results in an [ErrorConstructingResourceUrl](crate::CompendiumError::ErrorConstructingResourceUrl).
+    fn image_url(&self) -> Result<Url> {
+        Url::parse(self.image()).map_err(|_| CompendiumError::ErrorConstructingResourceUrl)
+    }
+
+    /// Whether the entry has an image url
+    fn has_image(&self) -> bool {
+        !self.image().is_empty()
+    }
+
+    /// Convert the entry's id, name and image to a [CEntry](crate::ffi::CEntry) for passing across a C FFI boundary.
+    /// See [CEntry](crate::ffi::CEntry) for the ownership and lifetime of its strings.
+    #[cfg(feature = "ffi")]
+    fn to_c_entry(&self) -> crate::ffi::CEntry {
+        crate::ffi::CEntry::new(self.id(), self.name(), self.image())
+    }
+
+    /// Render the entry as a markdown block, with a heading, image, description, details and lists of any locations or drops.
+    /// Fields specific to the entry's category (e.g. attack for equipment) are included in the details where present.
+    fn to_markdown(&self) -> String {
+        render_markdown(self, &[], None)
+    }
+}
+
+pub(crate) fn normalize_for_comparison(name: &str) -> Cow<'_, str> {
+    if is_normalized_for_comparison(name) {
+        return Cow::Borrowed(name);
+    }
+    Cow::Owned(
+        name.split_whitespace()
+            .map(str::to_lowercase)
+            .collect::<Vec<_>>()
+            .join(" "),
+    )
+}
+
+fn is_normalized_for_comparison(name: &str) -> bool {
+    let mut previous = ' ';
+    for c in name.chars() {
+        let is_invalid_space = c.is_whitespace() && (c != ' ' || previous == ' ');
+        if is_invalid_space || c.is_uppercase() {
+            return false;
+        }
+        previous = c;
+    }
+    name.is_empty() || previous != ' '
+}
+
+/// Convert hearts to the nearest whole number of quarter hearts, clamping negative and NaN values to 0 and huge values to `u32::MAX`
+#[cfg(any(feature = "creatures", feature = "materials"))]
+pub(crate) fn hearts_to_quarters(hearts: f32) -> u32 {
+    (hearts * 4.0).round().max(0.0) as u32
+}
+
+/// Render an entry as markdown with the category specific details and drops provided
+pub(crate) fn render_markdown<E: CompendiumEntry + ?Sized>(
+    entry: &E,
+    details: &[(&str, String)],
+    drops: Option<&Vec<String>>,
+) -> String {
+    let mut markdown = format!(
+        "## {}\n\n![{}]({})\n\n{}\n\n- **Id:** {}\n- **Category:** {}\n",
+        entry.name(),
+        entry.name(),
+        entry.image(),
+        entry.description(),
+        entry.id(),
+        entry.category_type()
+    );
+    for (label, value) in details {
+        let _ = writeln!(markdown, "- **{}:** {}", label, value);
+    }
+    write_markdown_list(&mut markdown, "Common locations", entry.common_locations());
+    write_markdown_list(&mut markdown, "Drops", drops);
+    markdown
+}
+
+/// Write an entry as a readable plain text summary with the category specific details and drops provided, for `Display` implementations
+pub(crate) fn render_summary<E: CompendiumEntry + ?Sized>(
+    entry: &E,
+    f: &mut fmt::Formatter<'_>,
+    details: &[(&str, String)],
+    drops: Option<&Vec<String>>,
+) -> fmt::Result {
+    write!(
+        f,
+        "{} (#{}, {})\n{}",
+        entry.name(),
+        entry.id(),
+        entry.category_type(),
+        entry.description()
+    )?;
+    for (label, value) in details {
+        write!(f, "\n{}: {}", label, value)?;
+    }
+    write_summary_list(f, "Common locations", entry.common_locations())?;
+    write_summary_list(f, "Drops", drops)
+}
+
+fn write_summary_list(
+    f: &mut fmt::Formatter<'_>,
+    label: &str,
+    items: Option<&Vec<String>>,
+) -> fmt::Result {
+    match items.filter(|items| !items.is_empty()) {
+        Some(items) => write!(f, "\n{}: {}", label, items.join(", ")),
+        None => Ok(()),
+    }
+}
+
+fn write_markdown_list(markdown: &mut String, heading: &str, items: Option<&Vec<String>>) {
+    if let Some(items) = items.filter(|items| !items.is_empty()) {
+        let _ = write!(markdown, "\n### {}\n\n", heading);
+        for item in items {
+            let _ = writeln!(markdown, "- {}", item);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(any(feature = "creatures", feature = "materials"))]
+    fn test_hearts_to_quarters() {
+        assert_eq!(0, hearts_to_quarters(0.0));
+        assert_eq!(1, hearts_to_quarters(0.25));
+        assert_eq!(2, hearts_to_quarters(0.5));
+        assert_eq!(4, hearts_to_quarters(1.0));
+        assert_eq!(12, hearts_to_quarters(3.0));
+        assert_eq!(5, hearts_to_quarters(1.26));
+        assert_eq!(0, hearts_to_quarters(-2.0));
+        assert_eq!(0, hearts_to_quarters(f32::NAN));
+        assert_eq!(u32::MAX, hearts_to_quarters(f32::INFINITY));
+    }
+
+    #[test]
+    fn test_normalize_for_comparison_borrows_normalized_names() {
+        assert!(matches!(
+            normalize_for_comparison("silver moblin"),
+            Cow::Borrowed("silver moblin")
+        ));
+        assert!(matches!(normalize_for_comparison(""), Cow::Borrowed("")));
+    }
+
+    #[test]
+    fn test_normalize_for_comparison_owns_changed_names() {
+        for name in [
+            "Silver Moblin",
+            " silver moblin",
+            "silver  moblin",
+            "silver\tmoblin",
+            "silver moblin ",
+        ] {
+            let normalized = normalize_for_comparison(name);
+            assert!(matches!(normalized, Cow::Owned(_)));
+            assert_eq!("silver moblin", normalized);
+        }
+    }
+}
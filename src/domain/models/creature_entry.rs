@@ -1,7 +1,12 @@
-use super::CommonEntry;
+use super::compendium_entry::{hearts_to_quarters, render_markdown, render_summary};
+use super::{CommonEntry, CompendiumEntry, HasDrops};
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::hash::{Hash, Hasher};
 
 /// A representation of a creature entry from the compendium
+///
+/// Entries are equal when all of their fields are, with hearts recovered compared by its exact bit pattern so that equality and hashing agree.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CreatureEntry {
     #[serde(flatten)]
@@ -9,7 +14,7 @@ pub struct CreatureEntry {
     drops: Option<Vec<String>>,
     hearts_recovered: Option<f32>,
     cooking_effect: Option<String>,
-    #[serde(default = "default_creature_category_type")]
+    #[serde(default = "default_creature_category_type", alias = "category")]
     category_type: String,
 }
 
@@ -39,16 +44,33 @@ impl CreatureEntry {
         self.common_fields.image()
     }
 
+    /// Get whether the entry is from downloadable content, which is `None` when the API doesn't say
+    pub fn dlc(&self) -> Option<bool> {
+        self.common_fields.dlc()
+    }
+
     /// Get the entry's drops
     pub fn drops(&self) -> Option<&Vec<String>> {
         self.drops.as_ref()
     }
 
+    /// Get the number of items the entry drops, which is 0 when the entry has no drops
+    pub fn drop_count(&self) -> usize {
+        HasDrops::drop_count(self)
+    }
+
     /// Get the entry's hearts recovered
     pub fn hearts_recovered(&self) -> Option<f32> {
         self.hearts_recovered
     }
 
+    /// Get the entry's hearts recovered as a whole number of quarter hearts (e.g. 1.25 hearts is 5), rounded to the nearest quarter.
+    /// Hearts recover in quarters, so comparing this avoids the pitfalls of comparing floats for equality.
+    /// Negative (or not a number) values are clamped to 0.
+    pub fn hearts_recovered_quarters(&self) -> Option<u32> {
+        self.hearts_recovered.map(hearts_to_quarters)
+    }
+
     /// Get the entry's hearts recovered
     pub fn cooking_effect(&self) -> Option<&String> {
         self.cooking_effect.as_ref()
@@ -60,6 +82,133 @@ impl CreatureEntry {
     }
 }
 
+impl CreatureEntry {
+    /// The creature specific details to render, as labelled values
+    fn details(&self) -> Vec<(&'static str, String)> {
+        let mut details = Vec::new();
+        if let Some(hearts_recovered) = self.hearts_recovered() {
+            details.push(("Hearts recovered", hearts_recovered.to_string()));
+        }
+        if let Some(cooking_effect) = self.cooking_effect().filter(|effect| !effect.is_empty()) {
+            details.push(("Cooking effect", cooking_effect.to_string()));
+        }
+        details
+    }
+}
+
+impl PartialEq for CreatureEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.common_fields == other.common_fields
+            && self.drops == other.drops
+            && self.cooking_effect == other.cooking_effect
+            && self.category_type == other.category_type
+            && self.hearts_recovered.map(f32::to_bits) == other.hearts_recovered.map(f32::to_bits)
+    }
+}
+
+impl Eq for CreatureEntry {}
+
+impl Hash for CreatureEntry {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.common_fields.hash(state);
+        self.drops.hash(state);
+        self.cooking_effect.hash(state);
+        self.hearts_recovered.map(f32::to_bits).hash(state);
+        self.category_type.hash(state);
+    }
+}
+
+impl CompendiumEntry for CreatureEntry {
+    fn id(&self) -> i32 {
+        self.id()
+    }
+
+    fn name(&self) -> &str {
+        self.name()
+    }
+
+    fn description(&self) -> &str {
+        self.description()
+    }
+
+    fn common_locations(&self) -> Option<&Vec<String>> {
+        self.common_locations()
+    }
+
+    fn image(&self) -> &str {
+        self.image()
+    }
+
+    fn dlc(&self) -> Option<bool> {
+        self.dlc()
+    }
+
+    fn category_type(&self) -> &str {
+        self.category_type()
+    }
+
+    fn to_markdown(&self) -> String {
+        render_markdown(self, &self.details(), self.drops())
+    }
+}
+
+/// Displays a readable multi-line summary of the entry: its name, id and category, description, hearts recovered, cooking effect, common locations and drops where present.
+/// The format is meant for people reading it and may change, so it shouldn't be parsed.
+impl fmt::Display for CreatureEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        render_summary(self, f, &self.details(), self.drops())
+    }
+}
+
+impl HasDrops for CreatureEntry {
+    fn drops(&self) -> Option<&Vec<String>> {
+        self.drops()
+    }
+}
+
 fn default_creature_category_type() -> String {
     String::from("creatures")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn creature(id: i32, hearts_recovered: f32) -> CreatureEntry {
+        serde_json::from_value(serde_json::json!({
+            "category": "creatures",
+            "common_locations": null,
+            "cooking_effect": "",
+            "description": "",
+            "hearts_recovered": hearts_recovered,
+            "id": id,
+            "image": "",
+            "name": "hyrule bass"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_creature_entry_equality() {
+        assert_eq!(creature(1, 1.5), creature(1, 1.5));
+        assert_ne!(creature(1, 1.5), creature(2, 1.5));
+        assert_ne!(creature(1, 1.5), creature(1, 2.0));
+    }
+
+    #[test]
+    fn test_creature_entry_hearts_recovered_quarters() {
+        assert_eq!(Some(0), creature(1, 0.0).hearts_recovered_quarters());
+        assert_eq!(Some(1), creature(1, 0.25).hearts_recovered_quarters());
+        assert_eq!(Some(8), creature(1, 2.0).hearts_recovered_quarters());
+    }
+
+    #[test]
+    fn test_creature_entry_hash_dedupes_equal_entries() {
+        let creatures: HashSet<CreatureEntry> =
+            [creature(1, 1.5), creature(1, 1.5), creature(2, 1.5)]
+                .into_iter()
+                .collect();
+        assert_eq!(2, creatures.len());
+    }
+}
@@ -0,0 +1,44 @@
+use super::CompendiumEntry;
+use serde::{Deserialize, Serialize};
+
+/// A lightweight summary of an entry from the compendium, containing only the fields needed for listing entries
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct EntrySummary {
+    id: i32,
+    name: String,
+    image: String,
+    category: String,
+}
+
+impl EntrySummary {
+    /// Get the entry's id
+    pub fn id(&self) -> i32 {
+        self.id
+    }
+
+    /// Get the entry's name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the entry's image
+    pub fn image(&self) -> &str {
+        &self.image
+    }
+
+    /// Get the entry's category
+    pub fn category(&self) -> &str {
+        &self.category
+    }
+}
+
+impl<T: CompendiumEntry + ?Sized> From<&T> for EntrySummary {
+    fn from(entry: &T) -> Self {
+        EntrySummary {
+            id: entry.id(),
+            name: entry.name().to_string(),
+            image: entry.image().to_string(),
+            category: entry.category_type().to_string(),
+        }
+    }
+}
@@ -1,14 +1,27 @@
-use super::CommonEntry;
+use super::compendium_entry::{render_markdown, render_summary};
+use super::{CommonEntry, CompendiumEntry};
 use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The kinds of equipment, as inferred from an equipment entry's attack and defense
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EquipmentKind {
+    /// Equipment with a positive attack, such as swords, spears and bows
+    Weapon,
+    /// Equipment with a positive defense but no attack, such as shields
+    Shield,
+    /// Equipment with neither a positive attack or defense, such as arrows
+    Other,
+}
 
 /// A representation of an equipment entry from the compendium
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct EquipmentEntry {
     #[serde(flatten)]
     common_fields: CommonEntry,
     attack: Option<i32>,
     defense: Option<i32>,
-    #[serde(default = "default_equipment_category_type")]
+    #[serde(default = "default_equipment_category_type", alias = "category")]
     category_type: String,
 }
 
@@ -38,6 +51,11 @@ impl EquipmentEntry {
         self.common_fields.image()
     }
 
+    /// Get whether the entry is from downloadable content, which is `None` when the API doesn't say
+    pub fn dlc(&self) -> Option<bool> {
+        self.common_fields.dlc()
+    }
+
     /// Get the entry's attack
     pub fn attack(&self) -> Option<i32> {
         self.attack
@@ -52,6 +70,72 @@ impl EquipmentEntry {
     pub fn category_type(&self) -> &str {
         self.category_type.as_str()
     }
+
+    /// Get the entry's [kind](EquipmentKind), inferred from its attack and defense as the API doesn't provide it.
+    /// Any entry with a positive attack is a weapon, otherwise any entry with a positive defense is a shield.
+    pub fn kind(&self) -> EquipmentKind {
+        match (self.attack.unwrap_or(0), self.defense.unwrap_or(0)) {
+            (attack, _) if attack > 0 => EquipmentKind::Weapon,
+            (_, defense) if defense > 0 => EquipmentKind::Shield,
+            _ => EquipmentKind::Other,
+        }
+    }
+}
+
+impl EquipmentEntry {
+    /// The equipment specific details to render, as labelled values
+    fn details(&self) -> Vec<(&'static str, String)> {
+        let mut details = Vec::new();
+        if let Some(attack) = self.attack() {
+            details.push(("Attack", attack.to_string()));
+        }
+        if let Some(defense) = self.defense() {
+            details.push(("Defense", defense.to_string()));
+        }
+        details
+    }
+}
+
+impl CompendiumEntry for EquipmentEntry {
+    fn id(&self) -> i32 {
+        self.id()
+    }
+
+    fn name(&self) -> &str {
+        self.name()
+    }
+
+    fn description(&self) -> &str {
+        self.description()
+    }
+
+    fn common_locations(&self) -> Option<&Vec<String>> {
+        self.common_locations()
+    }
+
+    fn image(&self) -> &str {
+        self.image()
+    }
+
+    fn dlc(&self) -> Option<bool> {
+        self.dlc()
+    }
+
+    fn category_type(&self) -> &str {
+        self.category_type()
+    }
+
+    fn to_markdown(&self) -> String {
+        render_markdown(self, &self.details(), None)
+    }
+}
+
+/// Displays a readable multi-line summary of the entry: its name, id and category, description, attack, defense and common locations where present.
+/// The format is meant for people reading it and may change, so it shouldn't be parsed.
+impl fmt::Display for EquipmentEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        render_summary(self, f, &self.details(), None)
+    }
 }
 
 fn default_equipment_category_type() -> String {
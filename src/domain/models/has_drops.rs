@@ -0,0 +1,50 @@
+/// The accessors shared by entries that can drop items, i.e. monsters, treasure and creatures
+pub trait HasDrops {
+    /// Get the entry's drops
+    fn drops(&self) -> Option<&Vec<String>>;
+
+    /// Get the number of items the entry drops, which is 0 when the entry has no drops
+    fn drop_count(&self) -> usize {
+        self.drops().map_or(0, Vec::len)
+    }
+}
+
+/// A drop normalized so the same drop differing only by case compares equal, e.g. "Amber" and "amber"
+pub(crate) fn normalize_drop(drop: &str) -> String {
+    drop.to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::models::{CreatureEntry, MonsterEntry, TreasureEntry};
+
+    fn entry_json(category: &str, drops: serde_json::Value) -> serde_json::Value {
+        serde_json::json!({
+            "category": category,
+            "common_locations": null,
+            "description": "",
+            "drops": drops,
+            "id": 1,
+            "image": "",
+            "name": "entry"
+        })
+    }
+
+    #[test]
+    fn test_drop_count() {
+        let monster: MonsterEntry =
+            serde_json::from_value(entry_json("monsters", serde_json::json!(null))).unwrap();
+        let treasure: TreasureEntry = serde_json::from_value(entry_json(
+            "treasure",
+            serde_json::json!(["rupee", "amber"]),
+        ))
+        .unwrap();
+        let creature: CreatureEntry =
+            serde_json::from_value(entry_json("creatures", serde_json::json!([]))).unwrap();
+        assert_eq!(0, monster.drop_count());
+        assert_eq!(2, treasure.drop_count());
+        assert_eq!(0, creature.drop_count());
+        assert_eq!(2, HasDrops::drop_count(&treasure));
+    }
+}
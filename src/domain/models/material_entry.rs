@@ -1,13 +1,18 @@
-use super::CommonEntry;
+use super::compendium_entry::{hearts_to_quarters, render_markdown, render_summary};
+use super::{CommonEntry, CompendiumEntry};
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::hash::{Hash, Hasher};
 
-/// A representation of a monster entry from the compendium
+/// A representation of a material entry from the compendium
+///
+/// Entries are equal when all of their fields are, with hearts recovered compared by its exact bit pattern so that equality and hashing agree.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct MaterialEntry {
     #[serde(flatten)]
     common_fields: CommonEntry,
     hearts_recovered: Option<f32>,
-    #[serde(default = "default_material_category_type")]
+    #[serde(default = "default_material_category_type", alias = "category")]
     category_type: String,
 }
 
@@ -37,17 +42,99 @@ impl MaterialEntry {
         self.common_fields.image()
     }
 
+    /// Get whether the entry is from downloadable content, which is `None` when the API doesn't say
+    pub fn dlc(&self) -> Option<bool> {
+        self.common_fields.dlc()
+    }
+
     /// Get the entry's hearts recovered
     pub fn hearts_recovered(&self) -> Option<f32> {
         self.hearts_recovered
     }
 
+    /// Get the entry's hearts recovered as a whole number of quarter hearts (e.g. 1.25 hearts is 5), rounded to the nearest quarter.
+    /// Hearts recover in quarters, so comparing this avoids the pitfalls of comparing floats for equality.
+    /// Negative (or not a number) values are clamped to 0.
+    pub fn hearts_recovered_quarters(&self) -> Option<u32> {
+        self.hearts_recovered.map(hearts_to_quarters)
+    }
+
     /// Get the entry's category type
     pub fn category_type(&self) -> &str {
         self.category_type.as_str()
     }
 }
 
+impl MaterialEntry {
+    /// The material specific details to render, as labelled values
+    fn details(&self) -> Vec<(&'static str, String)> {
+        self.hearts_recovered()
+            .map(|hearts_recovered| ("Hearts recovered", hearts_recovered.to_string()))
+            .into_iter()
+            .collect()
+    }
+}
+
+impl PartialEq for MaterialEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.common_fields == other.common_fields
+            && self.category_type == other.category_type
+            && self.hearts_recovered.map(f32::to_bits) == other.hearts_recovered.map(f32::to_bits)
+    }
+}
+
+impl Eq for MaterialEntry {}
+
+impl Hash for MaterialEntry {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.common_fields.hash(state);
+        self.hearts_recovered.map(f32::to_bits).hash(state);
+        self.category_type.hash(state);
+    }
+}
+
+impl CompendiumEntry for MaterialEntry {
+    fn id(&self) -> i32 {
+        self.id()
+    }
+
+    fn name(&self) -> &str {
+        self.name()
+    }
+
+    fn description(&self) -> &str {
+        self.description()
+    }
+
+    fn common_locations(&self) -> Option<&Vec<String>> {
+        self.common_locations()
+    }
+
+    fn image(&self) -> &str {
+        self.image()
+    }
+
+    fn dlc(&self) -> Option<bool> {
+        self.dlc()
+    }
+
+    fn category_type(&self) -> &str {
+        self.category_type()
+    }
+
+    fn to_markdown(&self) -> String {
+        render_markdown(self, &self.details(), None)
+    }
+}
+
+/// Displays a readable multi-line summary of the entry: its name, id and category, description, hearts recovered and common locations where present.
+/// The format is meant for people reading it and may change, so it shouldn't be parsed.
+impl fmt::Display for MaterialEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        render_summary(self, f, &self.details(), None)
+    }
+}
+
 fn default_material_category_type() -> String {
     String::from("materials")
 }
@@ -1,13 +1,28 @@
-use super::CommonEntry;
+use super::compendium_entry::{render_markdown, render_summary};
+use super::{CommonEntry, CompendiumEntry, HasDrops};
 use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// A rough ranking of how dangerous a monster is, as inferred from its name as the API doesn't provide monster stats
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DangerLevel {
+    /// Common monsters, such as bokoblins and keese
+    Low,
+    /// Stronger variants of common monsters, such as blue and black bokoblins
+    Medium,
+    /// The strongest variants of common monsters and guardians, such as silver moblins
+    High,
+    /// Large or unique monsters, such as lynels and hinoxes
+    Boss,
+}
 
 /// A representation of a monster entry from the compendium
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct MonsterEntry {
     #[serde(flatten)]
     common_fields: CommonEntry,
     drops: Option<Vec<String>>,
-    #[serde(default = "default_monster_category_type")]
+    #[serde(default = "default_monster_category_type", alias = "category")]
     category_type: String,
 }
 
@@ -37,17 +52,162 @@ impl MonsterEntry {
         self.common_fields.image()
     }
 
+    /// Get whether the entry is from downloadable content, which is `None` when the API doesn't say
+    pub fn dlc(&self) -> Option<bool> {
+        self.common_fields.dlc()
+    }
+
     /// Get the entry's drops
     pub fn drops(&self) -> Option<&Vec<String>> {
         self.drops.as_ref()
     }
 
+    /// Get the number of items the entry drops, which is 0 when the entry has no drops
+    pub fn drop_count(&self) -> usize {
+        HasDrops::drop_count(self)
+    }
+
     /// Get the entry's category type
     pub fn category_type(&self) -> &str {
         self.category_type.as_str()
     }
+
+    /// Get the entry's approximate [danger level](DangerLevel), inferred from keywords in its name (ignoring case).
+    /// Names containing "lynel", "hinox", "talus", "molduga" or "blight" are a boss, otherwise names containing "silver", "golden"
+    /// or "guardian" are high and names containing "black" or "blue" are medium, with anything else being low.
+    /// As it's based on the name alone it's only a heuristic, e.g. for sorting a bestiary.
+    pub fn danger_level(&self) -> DangerLevel {
+        let name = self.name().to_lowercase();
+        let contains_any =
+            |keywords: &[&str]| keywords.iter().any(|keyword| name.contains(keyword));
+        if contains_any(&["lynel", "hinox", "talus", "molduga", "blight"]) {
+            DangerLevel::Boss
+        } else if contains_any(&["silver", "golden", "guardian"]) {
+            DangerLevel::High
+        } else if contains_any(&["black", "blue"]) {
+            DangerLevel::Medium
+        } else {
+            DangerLevel::Low
+        }
+    }
+}
+
+impl CompendiumEntry for MonsterEntry {
+    fn id(&self) -> i32 {
+        self.id()
+    }
+
+    fn name(&self) -> &str {
+        self.name()
+    }
+
+    fn description(&self) -> &str {
+        self.description()
+    }
+
+    fn common_locations(&self) -> Option<&Vec<String>> {
+        self.common_locations()
+    }
+
+    fn image(&self) -> &str {
+        self.image()
+    }
+
+    fn dlc(&self) -> Option<bool> {
+        self.dlc()
+    }
+
+    fn category_type(&self) -> &str {
+        self.category_type()
+    }
+
+    fn to_markdown(&self) -> String {
+        render_markdown(self, &[], self.drops())
+    }
+}
+
+/// Displays a readable multi-line summary of the entry: its name, id and category, description, common locations and drops where present.
+/// The format is meant for people reading it and may change, so it shouldn't be parsed.
+impl fmt::Display for MonsterEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        render_summary(self, f, &[], self.drops())
+    }
+}
+
+impl HasDrops for MonsterEntry {
+    fn drops(&self) -> Option<&Vec<String>> {
+        self.drops()
+    }
 }
 
 fn default_monster_category_type() -> String {
     String::from("monsters")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monster(name: &str) -> MonsterEntry {
+        serde_json::from_value(serde_json::json!({
+            "category": "monsters",
+            "common_locations": null,
+            "description": "",
+            "drops": null,
+            "id": 1,
+            "image": "",
+            "name": name
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_monster_entry_image_url_requires_a_valid_url() {
+        assert!(matches!(
+            monster("bokoblin").image_url(),
+            Err(crate::CompendiumError::ErrorConstructingResourceUrl)
+        ));
+    }
+
+    fn monster_with_dlc(id: i32, dlc: Option<bool>) -> MonsterEntry {
+        serde_json::from_value(serde_json::json!({
+            "category": "monsters",
+            "common_locations": null,
+            "description": "",
+            "dlc": dlc,
+            "drops": null,
+            "id": id,
+            "image": "",
+            "name": "monster"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_monster_entry_is_dlc() {
+        assert!(monster_with_dlc(112, Some(true)).is_dlc());
+        assert!(!monster_with_dlc(112, Some(false)).is_dlc());
+        assert!(!monster_with_dlc(112, None).is_dlc());
+    }
+
+    #[test]
+    fn test_monster_entry_is_dlc_in_falls_back_to_id_ranges() {
+        let dlc_ids = [390..=395, 400..=400];
+        assert!(monster_with_dlc(392, None).is_dlc_in(&dlc_ids));
+        assert!(monster_with_dlc(400, None).is_dlc_in(&dlc_ids));
+        assert!(!monster_with_dlc(112, None).is_dlc_in(&dlc_ids));
+        // The API's own marker takes precedence over the ranges
+        assert!(monster_with_dlc(112, Some(true)).is_dlc_in(&dlc_ids));
+        assert!(!monster_with_dlc(392, Some(false)).is_dlc_in(&dlc_ids));
+    }
+
+    #[test]
+    fn test_monster_entry_danger_level() {
+        assert_eq!(DangerLevel::Low, monster("bokoblin").danger_level());
+        assert_eq!(DangerLevel::Medium, monster("Blue Bokoblin").danger_level());
+        assert_eq!(DangerLevel::High, monster("silver moblin").danger_level());
+        assert_eq!(DangerLevel::Boss, monster("golden lynel").danger_level());
+        assert_eq!(DangerLevel::Boss, monster("stone talus").danger_level());
+        assert!(DangerLevel::Low < DangerLevel::Boss);
+    }
+}
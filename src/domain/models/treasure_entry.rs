@@ -1,13 +1,15 @@
-use super::CommonEntry;
+use super::compendium_entry::{render_markdown, render_summary};
+use super::{CommonEntry, CompendiumEntry, HasDrops};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 /// A representation of a treasure entry from the compendium
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct TreasureEntry {
     #[serde(flatten)]
     common_fields: CommonEntry,
     drops: Option<Vec<String>>,
-    #[serde(default = "default_treasure_category_type")]
+    #[serde(default = "default_treasure_category_type", alias = "category")]
     category_type: String,
 }
 
@@ -37,17 +39,75 @@ impl TreasureEntry {
         self.common_fields.image()
     }
 
+    /// Get whether the entry is from downloadable content, which is `None` when the API doesn't say
+    pub fn dlc(&self) -> Option<bool> {
+        self.common_fields.dlc()
+    }
+
     /// Get the entry's drops
     pub fn drops(&self) -> Option<&Vec<String>> {
         self.drops.as_ref()
     }
 
+    /// Get the number of items the entry drops, which is 0 when the entry has no drops
+    pub fn drop_count(&self) -> usize {
+        HasDrops::drop_count(self)
+    }
+
     /// Get the entry's category type
     pub fn category_type(&self) -> &str {
         self.category_type.as_str()
     }
 }
 
+impl CompendiumEntry for TreasureEntry {
+    fn id(&self) -> i32 {
+        self.id()
+    }
+
+    fn name(&self) -> &str {
+        self.name()
+    }
+
+    fn description(&self) -> &str {
+        self.description()
+    }
+
+    fn common_locations(&self) -> Option<&Vec<String>> {
+        self.common_locations()
+    }
+
+    fn image(&self) -> &str {
+        self.image()
+    }
+
+    fn dlc(&self) -> Option<bool> {
+        self.dlc()
+    }
+
+    fn category_type(&self) -> &str {
+        self.category_type()
+    }
+
+    fn to_markdown(&self) -> String {
+        render_markdown(self, &[], self.drops())
+    }
+}
+
+/// Displays a readable multi-line summary of the entry: its name, id and category, description, common locations and drops where present.
+/// The format is meant for people reading it and may change, so it shouldn't be parsed.
+impl fmt::Display for TreasureEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        render_summary(self, f, &[], self.drops())
+    }
+}
+
+impl HasDrops for TreasureEntry {
+    fn drops(&self) -> Option<&Vec<String>> {
+        self.drops()
+    }
+}
+
 fn default_treasure_category_type() -> String {
     String::from("treasure")
 }
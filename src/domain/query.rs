@@ -0,0 +1,414 @@
+#[cfg(feature = "creatures")]
+use crate::domain::models::CreatureEntry;
+#[cfg(feature = "materials")]
+use crate::domain::models::MaterialEntry;
+#[cfg(feature = "monsters")]
+use crate::domain::models::{normalize_drop, CompendiumEntry, MonsterEntry};
+#[cfg(feature = "equipment")]
+use crate::domain::models::{EquipmentEntry, EquipmentKind};
+#[cfg(feature = "equipment")]
+use std::cmp::Reverse;
+#[cfg(any(feature = "creatures", feature = "materials"))]
+use std::collections::BTreeMap;
+#[cfg(feature = "monsters")]
+use std::collections::BTreeSet;
+
+/// Equipment entries grouped by their [kind](crate::domain::models::EquipmentKind)
+#[cfg(feature = "equipment")]
+#[derive(Clone, Debug, Default)]
+pub struct EquipmentByKind<'a> {
+    weapons: Vec<&'a EquipmentEntry>,
+    shields: Vec<&'a EquipmentEntry>,
+    other: Vec<&'a EquipmentEntry>,
+}
+
+#[cfg(feature = "equipment")]
+impl<'a> EquipmentByKind<'a> {
+    /// Get the weapon entries
+    pub fn weapons(&self) -> &Vec<&'a EquipmentEntry> {
+        &self.weapons
+    }
+
+    /// Get the shield entries
+    pub fn shields(&self) -> &Vec<&'a EquipmentEntry> {
+        &self.shields
+    }
+
+    /// Get the entries that are neither weapons or shields
+    pub fn other(&self) -> &Vec<&'a EquipmentEntry> {
+        &self.other
+    }
+}
+
+/// Group equipment entries by their [kind](crate::domain::models::EquipmentEntry::kind).
+/// As the API doesn't provide the kind of equipment it's inferred from attack and defense,
+/// so bows are grouped with weapons and anything without attack or defense (e.g. arrows) is grouped as other.
+#[cfg(feature = "equipment")]
+pub fn equipment_by_kind(equipment: &[EquipmentEntry]) -> EquipmentByKind<'_> {
+    equipment
+        .iter()
+        .fold(EquipmentByKind::default(), |mut by_kind, entry| {
+            match entry.kind() {
+                EquipmentKind::Weapon => by_kind.weapons.push(entry),
+                EquipmentKind::Shield => by_kind.shields.push(entry),
+                EquipmentKind::Other => by_kind.other.push(entry),
+            }
+            by_kind
+        })
+}
+
+/// Get the `n` equipment entries with the highest attack, highest first with ties ordered by id.
+/// Entries without an attack value are excluded, so fewer than `n` entries may be returned.
+#[cfg(feature = "equipment")]
+pub fn top_by_attack(equipment: &[EquipmentEntry], n: usize) -> Vec<&EquipmentEntry> {
+    top_by(equipment, n, EquipmentEntry::attack)
+}
+
+/// Get the `n` equipment entries with the highest defense, highest first with ties ordered by id.
+/// Entries without a defense value are excluded, so fewer than `n` entries may be returned.
+#[cfg(feature = "equipment")]
+pub fn top_by_defense(equipment: &[EquipmentEntry], n: usize) -> Vec<&EquipmentEntry> {
+    top_by(equipment, n, EquipmentEntry::defense)
+}
+
+/// Rank equipment entries by the given metric (e.g. attack, or attack plus defense), highest first with ties ordered by id.
+/// Entries where the metric is NaN are excluded.
+#[cfg(feature = "equipment")]
+pub fn rank_equipment_by<F: Fn(&EquipmentEntry) -> f64>(
+    equipment: &[EquipmentEntry],
+    metric: F,
+) -> Vec<&EquipmentEntry> {
+    let mut ranked: Vec<(f64, &EquipmentEntry)> = equipment
+        .iter()
+        .map(|entry| (metric(entry), entry))
+        .filter(|(value, _)| !value.is_nan())
+        .collect();
+    ranked
+        .sort_by(|(a_value, a), (b_value, b)| b_value.total_cmp(a_value).then(a.id().cmp(&b.id())));
+    ranked.into_iter().map(|(_, entry)| entry).collect()
+}
+
+#[cfg(feature = "equipment")]
+fn top_by(
+    equipment: &[EquipmentEntry],
+    n: usize,
+    stat: fn(&EquipmentEntry) -> Option<i32>,
+) -> Vec<&EquipmentEntry> {
+    let mut ranked = rank_equipment_by(equipment, |entry| stat(entry).map_or(f64::NAN, f64::from));
+    ranked.truncate(n);
+    ranked
+}
+
+/// Sorting for equipment entries, e.g. `all_entries.equipment().sorted_by_attack_desc()` for a list of the best weapons
+#[cfg(feature = "equipment")]
+pub trait EquipmentSort {
+    /// Get every equipment entry sorted by attack, highest first with ties ordered by id.
+    /// Entries without an attack value are treated as the lowest, so they come last.
+    fn sorted_by_attack_desc(&self) -> Vec<&EquipmentEntry>;
+
+    /// Get every equipment entry sorted by defense, highest first with ties ordered by id.
+    /// Entries without a defense value are treated as the lowest, so they come last.
+    fn sorted_by_defense_desc(&self) -> Vec<&EquipmentEntry>;
+}
+
+#[cfg(feature = "equipment")]
+impl EquipmentSort for [EquipmentEntry] {
+    fn sorted_by_attack_desc(&self) -> Vec<&EquipmentEntry> {
+        sorted_desc_by(self, EquipmentEntry::attack)
+    }
+
+    fn sorted_by_defense_desc(&self) -> Vec<&EquipmentEntry> {
+        sorted_desc_by(self, EquipmentEntry::defense)
+    }
+}
+
+#[cfg(feature = "equipment")]
+fn sorted_desc_by(
+    equipment: &[EquipmentEntry],
+    stat: fn(&EquipmentEntry) -> Option<i32>,
+) -> Vec<&EquipmentEntry> {
+    let mut sorted: Vec<&EquipmentEntry> = equipment.iter().collect();
+    sorted.sort_by_key(|entry| (Reverse(stat(entry)), entry.id()));
+    sorted
+}
+
+/// A range of hearts recovered by an entry, ordered from no recovery to the most hearts
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HeartBucket {
+    /// The entry doesn't state how many hearts it recovers
+    NoRecovery,
+    /// Less than 1 heart
+    Zero,
+    /// At least 1 heart but less than 3
+    OneToTwo,
+    /// At least 3 hearts but less than 5
+    ThreeToFour,
+    /// 5 hearts or more
+    FivePlus,
+}
+
+impl HeartBucket {
+    /// The bucket for the given hearts recovered, where fractional hearts fall into the bucket of the whole hearts below them
+    pub fn from_hearts(hearts_recovered: Option<f32>) -> Self {
+        match hearts_recovered {
+            None => HeartBucket::NoRecovery,
+            Some(hearts) if hearts < 1.0 => HeartBucket::Zero,
+            Some(hearts) if hearts < 3.0 => HeartBucket::OneToTwo,
+            Some(hearts) if hearts < 5.0 => HeartBucket::ThreeToFour,
+            Some(_) => HeartBucket::FivePlus,
+        }
+    }
+}
+
+/// Group creature entries by the [range of hearts](HeartBucket) they recover, keeping the order of the given entries within each bucket.
+/// Only buckets containing at least one entry are present.
+#[cfg(feature = "creatures")]
+pub fn partition_by_hearts(
+    entries: &[CreatureEntry],
+) -> BTreeMap<HeartBucket, Vec<&CreatureEntry>> {
+    partition_by(entries, CreatureEntry::hearts_recovered)
+}
+
+/// Group material entries by the [range of hearts](HeartBucket) they recover, keeping the order of the given entries within each bucket.
+/// Only buckets containing at least one entry are present.
+#[cfg(feature = "materials")]
+pub fn partition_materials_by_hearts(
+    entries: &[MaterialEntry],
+) -> BTreeMap<HeartBucket, Vec<&MaterialEntry>> {
+    partition_by(entries, MaterialEntry::hearts_recovered)
+}
+
+#[cfg(any(feature = "creatures", feature = "materials"))]
+fn partition_by<T>(entries: &[T], hearts: fn(&T) -> Option<f32>) -> BTreeMap<HeartBucket, Vec<&T>> {
+    entries.iter().fold(BTreeMap::new(), |mut buckets, entry| {
+        buckets
+            .entry(HeartBucket::from_hearts(hearts(entry)))
+            .or_insert_with(Vec::new)
+            .push(entry);
+        buckets
+    })
+}
+
+/// Pair each master mode monster with the standard monster of the same [normalized name](crate::domain::models::CompendiumEntry::name_normalized), in the order of the master mode monsters.
+/// Monsters exclusive to master mode are paired with `None`.
+#[cfg(feature = "monsters")]
+pub fn pair_by_name<'a>(
+    master: &'a [MonsterEntry],
+    standard: &'a [MonsterEntry],
+) -> Vec<(&'a MonsterEntry, Option<&'a MonsterEntry>)> {
+    master
+        .iter()
+        .map(|master_monster| {
+            let master_name = master_monster.name_normalized();
+            let standard_monster = standard
+                .iter()
+                .find(|standard_monster| standard_monster.name_normalized() == master_name);
+            (master_monster, standard_monster)
+        })
+        .collect()
+}
+
+/// Get every drop from the given monsters, e.g. to see what can be collected on a farming route.
+/// Drops are lowercased so the same drop differing only by case is listed once, and sorted alphabetically.
+#[cfg(feature = "monsters")]
+pub fn combined_drops(monsters: &[&MonsterEntry]) -> BTreeSet<String> {
+    monsters
+        .iter()
+        .filter_map(|monster| monster.drops())
+        .flatten()
+        .map(|drop| normalize_drop(drop))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn equipment_data<'a>() -> &'a str {
+        r#"[{"attack":30,"category":"equipment","common_locations":["Hyrule Castle"],"defense":0,"description":"The legendary sword that seals the darkness.","id":347,"image":"https://botw-compendium.herokuapp.com/api/v2/entry/master_sword/image","name":"master sword"},{"attack":0,"category":"equipment","common_locations":["Hyrule Castle"],"defense":90,"description":"The Hylian Shield.","id":371,"image":"https://botw-compendium.herokuapp.com/api/v2/entry/hylian_shield/image","name":"hylian shield"},{"attack":null,"category":"equipment","common_locations":null,"defense":null,"description":"A standard arrow.","id":330,"image":"https://botw-compendium.herokuapp.com/api/v2/entry/arrow/image","name":"arrow"},{"attack":20,"category":"equipment","common_locations":["Hyrule Castle"],"defense":0,"description":"A bow of the royal guard.","id":316,"image":"https://botw-compendium.herokuapp.com/api/v2/entry/royal_bow/image","name":"royal bow"}]"#
+    }
+
+    fn equipment() -> Vec<EquipmentEntry> {
+        serde_json::from_str(equipment_data()).unwrap()
+    }
+
+    fn monster(id: i32, name: &str) -> MonsterEntry {
+        serde_json::from_value(serde_json::json!({
+            "category": "monsters",
+            "common_locations": null,
+            "description": "",
+            "drops": null,
+            "id": id,
+            "image": "",
+            "name": name
+        }))
+        .unwrap()
+    }
+
+    fn ids(entries: &[&EquipmentEntry]) -> Vec<i32> {
+        entries.iter().map(|entry| entry.id()).collect()
+    }
+
+    #[test]
+    fn test_equipment_by_kind() {
+        let equipment = equipment();
+        let by_kind = equipment_by_kind(&equipment);
+        assert_eq!(vec![347, 316], ids(by_kind.weapons()));
+        assert_eq!(vec![371], ids(by_kind.shields()));
+        assert_eq!(vec![330], ids(by_kind.other()));
+    }
+
+    #[test]
+    fn test_top_by_attack() {
+        let equipment = equipment();
+        assert_eq!(vec![347, 316], ids(&top_by_attack(&equipment, 2)));
+        assert_eq!(vec![347, 316, 371], ids(&top_by_attack(&equipment, 10)));
+    }
+
+    #[test]
+    fn test_top_by_defense_orders_ties_by_id() {
+        let equipment = equipment();
+        assert_eq!(vec![371, 316, 347], ids(&top_by_defense(&equipment, 10)));
+        assert!(top_by_defense(&equipment, 0).is_empty());
+    }
+
+    #[test]
+    fn test_sorted_by_attack_desc_puts_missing_attack_last() {
+        let equipment = equipment();
+        assert_eq!(
+            vec![347, 316, 371, 330],
+            ids(&equipment.sorted_by_attack_desc())
+        );
+        assert!(Vec::<EquipmentEntry>::new()
+            .sorted_by_attack_desc()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_sorted_by_defense_desc_orders_ties_by_id() {
+        let equipment = equipment();
+        assert_eq!(
+            vec![371, 316, 347, 330],
+            ids(&equipment.sorted_by_defense_desc())
+        );
+    }
+
+    fn creature(id: i32, hearts_recovered: Option<f32>) -> CreatureEntry {
+        serde_json::from_value(serde_json::json!({
+            "category": "creatures",
+            "common_locations": null,
+            "description": "",
+            "hearts_recovered": hearts_recovered,
+            "id": id,
+            "image": "",
+            "name": "creature"
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_heart_bucket_from_hearts() {
+        assert_eq!(HeartBucket::NoRecovery, HeartBucket::from_hearts(None));
+        assert_eq!(HeartBucket::Zero, HeartBucket::from_hearts(Some(0.5)));
+        assert_eq!(HeartBucket::OneToTwo, HeartBucket::from_hearts(Some(1.0)));
+        assert_eq!(HeartBucket::OneToTwo, HeartBucket::from_hearts(Some(2.5)));
+        assert_eq!(
+            HeartBucket::ThreeToFour,
+            HeartBucket::from_hearts(Some(4.0))
+        );
+        assert_eq!(HeartBucket::FivePlus, HeartBucket::from_hearts(Some(5.0)));
+    }
+
+    #[test]
+    fn test_partition_by_hearts() {
+        let creatures = vec![
+            creature(1, None),
+            creature(2, Some(0.0)),
+            creature(3, Some(2.0)),
+            creature(4, Some(1.0)),
+            creature(5, Some(12.0)),
+        ];
+        let buckets = partition_by_hearts(&creatures);
+        let ids: Vec<(HeartBucket, Vec<i32>)> = buckets
+            .iter()
+            .map(|(bucket, entries)| (*bucket, entries.iter().map(|entry| entry.id()).collect()))
+            .collect();
+        assert_eq!(
+            vec![
+                (HeartBucket::NoRecovery, vec![1]),
+                (HeartBucket::Zero, vec![2]),
+                (HeartBucket::OneToTwo, vec![3, 4]),
+                (HeartBucket::FivePlus, vec![5]),
+            ],
+            ids
+        );
+    }
+
+    #[test]
+    fn test_rank_equipment_by() {
+        let equipment = equipment();
+        let ranked = rank_equipment_by(&equipment, |entry| {
+            f64::from(entry.attack().unwrap_or(0) + entry.defense().unwrap_or(0))
+        });
+        assert_eq!(vec![371, 347, 316, 330], ids(&ranked));
+
+        let ranked = rank_equipment_by(&equipment, |entry| {
+            f64::from(entry.defense().unwrap_or(0)) / f64::from(entry.attack().unwrap_or(0))
+        });
+        assert_eq!(vec![371, 316, 347], ids(&ranked));
+    }
+
+    #[test]
+    fn test_pair_by_name() {
+        let master = vec![
+            monster(1, "Silver Moblin"),
+            monster(2, "golden moblin"),
+            monster(3, " Black  Bokoblin"),
+        ];
+        let standard = vec![
+            monster(112, "silver moblin"),
+            monster(104, "bokoblin"),
+            monster(105, "black bokoblin"),
+        ];
+        let pairs = pair_by_name(&master, &standard);
+        assert_eq!(3, pairs.len());
+        assert_eq!(1, pairs[0].0.id());
+        assert_eq!(Some(112), pairs[0].1.map(MonsterEntry::id));
+        assert_eq!(2, pairs[1].0.id());
+        assert!(pairs[1].1.is_none());
+        assert_eq!(3, pairs[2].0.id());
+        assert_eq!(Some(105), pairs[2].1.map(MonsterEntry::id));
+    }
+
+    #[test]
+    fn test_combined_drops() {
+        let lizalfos: MonsterEntry = serde_json::from_value(serde_json::json!({
+            "category": "monsters",
+            "common_locations": null,
+            "description": "",
+            "drops": ["lizalfos horn", "Lizalfos Talon"],
+            "id": 90,
+            "image": "",
+            "name": "lizalfos"
+        }))
+        .unwrap();
+        let moblin: MonsterEntry = serde_json::from_value(serde_json::json!({
+            "category": "monsters",
+            "common_locations": null,
+            "description": "",
+            "drops": ["moblin horn", "lizalfos talon"],
+            "id": 112,
+            "image": "",
+            "name": "silver moblin"
+        }))
+        .unwrap();
+        let without_drops = monster(1, "stone talus");
+        let drops: Vec<String> = combined_drops(&[&lizalfos, &moblin, &without_drops])
+            .into_iter()
+            .collect();
+        assert_eq!(
+            vec!["lizalfos horn", "lizalfos talon", "moblin horn"],
+            drops
+        );
+    }
+}
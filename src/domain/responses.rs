@@ -1,99 +1,1139 @@
+use crate::api::data_from_json_str;
+#[cfg(feature = "creatures")]
+use crate::domain::inputs::Situation;
+use crate::domain::inputs::{CompendiumCategory, EntryIdentifier};
+#[cfg(feature = "creatures")]
+use crate::domain::models::CreatureEntry;
+#[cfg(feature = "equipment")]
+use crate::domain::models::EquipmentEntry;
+#[cfg(feature = "materials")]
+use crate::domain::models::MaterialEntry;
+#[cfg(feature = "monsters")]
+use crate::domain::models::MonsterEntry;
+#[cfg(feature = "treasure")]
+use crate::domain::models::TreasureEntry;
 use crate::domain::models::{
-    CreatureEntry, EquipmentEntry, MaterialEntry, MonsterEntry, TreasureEntry,
+    normalize_drop, normalize_for_comparison, render_markdown, CompendiumEntry, EntrySummary,
+    HasDrops,
 };
-use serde::{Deserialize, Serialize};
+use crate::error::CompendiumError;
+use crate::result::Result;
+use serde::de::{DeserializeOwned, Error as _};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{BTreeSet, HashMap};
 
-/// A representation of all entries from the compendium
-#[derive(Clone, Debug, Serialize, Deserialize)]
+/// A representation of all entries from the compendium.
+/// Only the categories whose features are enabled are kept, the rest of the API's response is ignored.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct AllStandardEntries {
+    #[cfg(feature = "creatures")]
     creatures: AllCreatureEntries,
+    #[cfg(feature = "equipment")]
     equipment: Vec<EquipmentEntry>,
+    #[cfg(feature = "materials")]
     materials: Vec<MaterialEntry>,
+    #[cfg(feature = "monsters")]
     monsters: Vec<MonsterEntry>,
+    #[cfg(feature = "treasure")]
     treasure: Vec<TreasureEntry>,
 }
 
 impl AllStandardEntries {
+    /// Load all entries from JSON in the shape the API returns them, i.e. wrapped in `{"data": ...}`, such as a saved response from `all`.
+    /// Invalid JSON results in a [DeserializationError](crate::CompendiumError::DeserializationError).
+    pub fn from_json_str(json: &str) -> Result<Self> {
+        data_from_json_str(json)
+    }
+
     /// A reference to the creature entries from the compendium
+    #[cfg(feature = "creatures")]
     pub fn creatures(&self) -> &AllCreatureEntries {
         &self.creatures
     }
 
     /// A reference to the equipment entries from the compendium
+    #[cfg(feature = "equipment")]
     pub fn equipment(&self) -> &Vec<EquipmentEntry> {
         &self.equipment
     }
 
     /// A reference to the material entries from the compendium
+    #[cfg(feature = "materials")]
     pub fn materials(&self) -> &Vec<MaterialEntry> {
         &self.materials
     }
 
     /// A reference to the monster entries from the compendium
+    #[cfg(feature = "monsters")]
     pub fn monsters(&self) -> &Vec<MonsterEntry> {
         &self.monsters
     }
 
     /// A reference to the treasure entries from the compendium
+    #[cfg(feature = "treasure")]
     pub fn treasure(&self) -> &Vec<TreasureEntry> {
         &self.treasure
     }
 
     /// A mutable reference to the creature entries from the compendium
+    #[cfg(feature = "creatures")]
     pub fn creatures_mut(&mut self) -> &mut AllCreatureEntries {
         &mut self.creatures
     }
 
     /// A mutable reference to the equipment entries from the compendium
+    #[cfg(feature = "equipment")]
     pub fn equipment_mut(&mut self) -> &mut Vec<EquipmentEntry> {
         &mut self.equipment
     }
 
     /// A mutable reference to the materials entries from the compendium
+    #[cfg(feature = "materials")]
     pub fn materials_mut(&mut self) -> &mut Vec<MaterialEntry> {
         &mut self.materials
     }
 
     /// A mutable reference to the monster entries from the compendium
+    #[cfg(feature = "monsters")]
     pub fn monsters_mut(&mut self) -> &mut Vec<MonsterEntry> {
         &mut self.monsters
     }
 
     /// A mutable reference to the treasure entries from the compendium
+    #[cfg(feature = "treasure")]
     pub fn treasure_mut(&mut self) -> &mut Vec<TreasureEntry> {
         &mut self.treasure
     }
+
+    /// Split the entries into those from the base game and those from downloadable content, in that order.
+    /// Entries that don't say whether they're from downloadable content are treated as from the base game.
+    pub fn partition_dlc(&self) -> (Vec<&dyn CompendiumEntry>, Vec<&dyn CompendiumEntry>) {
+        self.entries().partition(|entry| !entry.is_dlc())
+    }
+
+    /// Keep only the entries in the given categories, emptying the rest to free their memory.
+    /// Keeping the creature category keeps both the food and non-food creatures.
+    pub fn retain_categories(mut self, categories: &[CompendiumCategory]) -> AllStandardEntries {
+        let keep = |category| categories.contains(&category);
+        #[cfg(feature = "creatures")]
+        if !keep(CompendiumCategory::Creature) {
+            self.creatures = AllCreatureEntries::default();
+        }
+        #[cfg(feature = "equipment")]
+        if !keep(CompendiumCategory::Equipment) {
+            self.equipment = Vec::new();
+        }
+        #[cfg(feature = "materials")]
+        if !keep(CompendiumCategory::Material) {
+            self.materials = Vec::new();
+        }
+        #[cfg(feature = "monsters")]
+        if !keep(CompendiumCategory::Monster) {
+            self.monsters = Vec::new();
+        }
+        #[cfg(feature = "treasure")]
+        if !keep(CompendiumCategory::Treasure) {
+            self.treasure = Vec::new();
+        }
+        self
+    }
+
+    /// The monster entries with no drops, which usually means the entry is incomplete
+    #[cfg(feature = "monsters")]
+    pub fn monsters_without_drops(&self) -> Vec<&MonsterEntry> {
+        self.monsters
+            .iter()
+            .filter(|monster| monster.drops().is_none_or(Vec::is_empty))
+            .collect()
+    }
+
+    /// The treasure entries with no drops, which usually means the entry is incomplete
+    #[cfg(feature = "treasure")]
+    pub fn treasure_without_drops(&self) -> Vec<&TreasureEntry> {
+        self.treasure
+            .iter()
+            .filter(|treasure| treasure.drops().is_none_or(Vec::is_empty))
+            .collect()
+    }
+
+    /// Insert an entry into the matching category, replacing any existing entry in that category with the same id.
+    /// Creatures are inserted as food when they have either a cooking effect or hearts recovered, otherwise as non-food.
+    /// An [Unknown](EntryResponse::Unknown) entry has no category to be inserted into, so it's ignored.
+    /// Returns whether an existing entry was replaced.
+    pub fn insert(&mut self, entry: EntryResponse) -> bool {
+        match entry {
+            #[cfg(feature = "creatures")]
+            EntryResponse::Creature(creature) => {
+                let is_food =
+                    creature.cooking_effect().is_some() || creature.hearts_recovered().is_some();
+                let (bucket, other_bucket) = if is_food {
+                    (&mut self.creatures.food, &mut self.creatures.non_food)
+                } else {
+                    (&mut self.creatures.non_food, &mut self.creatures.food)
+                };
+                let existing_count = other_bucket.len();
+                other_bucket.retain(|existing| existing.id() != creature.id());
+                let moved = other_bucket.len() != existing_count;
+                upsert_entry(bucket, creature) || moved
+            }
+            #[cfg(feature = "equipment")]
+            EntryResponse::Equipment(equipment) => upsert_entry(&mut self.equipment, equipment),
+            #[cfg(feature = "materials")]
+            EntryResponse::Material(material) => upsert_entry(&mut self.materials, material),
+            #[cfg(feature = "monsters")]
+            EntryResponse::Monster(monster) => upsert_entry(&mut self.monsters, monster),
+            #[cfg(feature = "treasure")]
+            EntryResponse::Treasure(treasure) => upsert_entry(&mut self.treasure, treasure),
+            EntryResponse::Unknown { .. } => false,
+        }
+    }
+
+    /// A printable table of the number of entries in each category, including the split of food and non-food creatures.
+    /// Only the categories whose features are enabled are included.
+    pub fn breakdown_table(&self) -> String {
+        let rows = [
+            #[cfg(feature = "creatures")]
+            (
+                "Creatures",
+                self.creatures.food.len() + self.creatures.non_food.len(),
+            ),
+            #[cfg(feature = "creatures")]
+            ("  Food", self.creatures.food.len()),
+            #[cfg(feature = "creatures")]
+            ("  Non-food", self.creatures.non_food.len()),
+            #[cfg(feature = "equipment")]
+            ("Equipment", self.equipment.len()),
+            #[cfg(feature = "materials")]
+            ("Materials", self.materials.len()),
+            #[cfg(feature = "monsters")]
+            ("Monsters", self.monsters.len()),
+            #[cfg(feature = "treasure")]
+            ("Treasure", self.treasure.len()),
+        ];
+        let total = self.entries().count();
+        let divider = format!("{:-<12}+{:-<7}\n", "", "");
+
+        let mut table = format!("{:<12}| {:>5}\n", "Category", "Count");
+        table.push_str(&divider);
+        for (category, count) in rows {
+            table.push_str(&format!("{:<12}| {:>5}\n", category, count));
+        }
+        table.push_str(&divider);
+        table.push_str(&format!("{:<12}| {:>5}\n", "Total", total));
+        table
+    }
+
+    /// A lightweight [summary](crate::domain::models::EntrySummary) of every entry from the compendium
+    pub fn summaries(&self) -> Vec<EntrySummary> {
+        self.entries().map(EntrySummary::from).collect()
+    }
+
+    /// An index of every entry by its [normalized name](crate::domain::models::CompendiumEntry::name_normalized), in the order the API returned them.
+    /// When more than one entry has the same name, the first entry is kept.
+    #[cfg(feature = "indexmap")]
+    pub fn index_by_name_ordered(&self) -> indexmap::IndexMap<String, &dyn CompendiumEntry> {
+        let mut index = indexmap::IndexMap::new();
+        for entry in self.entries() {
+            index
+                .entry(entry.name_normalized().into_owned())
+                .or_insert(entry);
+        }
+        index
+    }
+
+    /// An index of every drop (lowercased) to the creature, monster and treasure entries that drop it
+    pub fn drop_index(&self) -> HashMap<String, Vec<&dyn CompendiumEntry>> {
+        let entries_with_drops = std::iter::empty::<(&dyn CompendiumEntry, Option<&Vec<String>>)>();
+        #[cfg(feature = "creatures")]
+        let entries_with_drops = entries_with_drops.chain(
+            self.creatures
+                .food
+                .iter()
+                .chain(self.creatures.non_food.iter())
+                .map(|creature| (as_entry(creature), creature.drops())),
+        );
+        #[cfg(feature = "monsters")]
+        let entries_with_drops = entries_with_drops.chain(
+            self.monsters
+                .iter()
+                .map(|monster| (as_entry(monster), monster.drops())),
+        );
+        #[cfg(feature = "treasure")]
+        let entries_with_drops = entries_with_drops.chain(
+            self.treasure
+                .iter()
+                .map(|treasure| (as_entry(treasure), treasure.drops())),
+        );
+
+        let mut index: HashMap<String, Vec<&dyn CompendiumEntry>> = HashMap::new();
+        for (entry, drops) in entries_with_drops {
+            for drop in drops.into_iter().flatten() {
+                index.entry(normalize_drop(drop)).or_default().push(entry);
+            }
+        }
+        index
+    }
+
+    /// Every distinct common location across all entries from the compendium, sorted alphabetically
+    pub fn all_locations(&self) -> BTreeSet<String> {
+        self.entries()
+            .filter_map(|entry| entry.common_locations())
+            .flatten()
+            .cloned()
+            .collect()
+    }
+
+    /// The number of entries that list each common location, counting an entry once for each distinct location it lists
+    pub fn counts_by_location(&self) -> HashMap<String, usize> {
+        let mut counts = HashMap::new();
+        for entry in self.entries() {
+            let locations: BTreeSet<&String> =
+                entry.common_locations().into_iter().flatten().collect();
+            for location in locations {
+                *counts.entry(location.to_string()).or_insert(0) += 1;
+            }
+        }
+        counts
+    }
+
+    /// How often each pair of common locations appears together on the same entry.
+    /// Each pair is ordered alphabetically so it's only counted once, and repeated locations on an entry are ignored.
+    pub fn location_cooccurrence(&self) -> HashMap<(String, String), usize> {
+        let mut cooccurrence = HashMap::new();
+        for entry in self.entries() {
+            let locations: BTreeSet<&String> =
+                entry.common_locations().into_iter().flatten().collect();
+            let locations: Vec<&String> = locations.into_iter().collect();
+            for (index, first) in locations.iter().enumerate() {
+                for second in &locations[index + 1..] {
+                    *cooccurrence
+                        .entry((first.to_string(), second.to_string()))
+                        .or_insert(0) += 1;
+                }
+            }
+        }
+        cooccurrence
+    }
+
+    /// The fraction (between 0.0 and 1.0) of entries from the compendium that have an image url, or 0.0 when there are no entries
+    pub fn image_coverage(&self) -> f32 {
+        let (total, with_image) = self.entries().fold((0, 0), |(total, with_image), entry| {
+            (total + 1, with_image + usize::from(entry.has_image()))
+        });
+        if total == 0 {
+            return 0.0;
+        }
+        with_image as f32 / total as f32
+    }
+
+    /// The entries whose description is shorter than `min` or longer than `max` characters, which may indicate a truncated or malformed entry.
+    /// Lengths are counted in characters rather than bytes, and both bounds are inclusive.
+    pub fn descriptions_outside(&self, min: usize, max: usize) -> Vec<&dyn CompendiumEntry> {
+        self.entries()
+            .filter(|entry| {
+                let length = entry.description().chars().count();
+                length < min || length > max
+            })
+            .collect()
+    }
+
+    /// The entries whose name contains a non-ASCII character, such as an accented letter, which are the most likely to be mishandled when slugged into a url
+    pub fn non_ascii_names(&self) -> Vec<&dyn CompendiumEntry> {
+        self.entries()
+            .filter(|entry| !entry.name().is_ascii())
+            .collect()
+    }
+
+    /// Whether an entry matching the identifier is present, e.g. to validate an identifier before requesting it.
+    /// Names are matched the way the API matches them: ignoring case and treating underscores as spaces, see [name_normalized](crate::domain::models::CompendiumEntry::name_normalized).
+    pub fn contains(&self, identifier: EntryIdentifier) -> bool {
+        match identifier {
+            EntryIdentifier::Id(id) => self.entries().any(|entry| entry.id() == id),
+            EntryIdentifier::Name(name) => self.contains_name(name),
+            EntryIdentifier::OwnedName(name) => self.contains_name(&name),
+        }
+    }
+
+    fn contains_name(&self, name: &str) -> bool {
+        let name = name.replace('_', " ");
+        let name = normalize_for_comparison(&name);
+        self.entries().any(|entry| entry.name_normalized() == name)
+    }
+
+    /// Find the entries whose name is close to the query, best match first, e.g. to suggest entries for a mistyped name.
+    /// Names are compared ignoring case, an entry matches when its name contains the query or is within `max_distance`
+    /// single character edits ([Levenshtein distance](https://en.wikipedia.org/wiki/Levenshtein_distance)) of it.
+    /// Names containing the query rank first, then entries are ordered by distance and finally by id.
+    pub fn find_by_name_fuzzy(&self, query: &str, max_distance: usize) -> Vec<EntryResponse> {
+        let query = query.replace('_', " ");
+        let query = normalize_for_comparison(&query);
+        let mut matches: Vec<((bool, usize, i32), EntryResponse)> = self
+            .iter()
+            .filter_map(|entry| {
+                let name = entry.name_normalized();
+                let contains_query = name.contains(query.as_ref());
+                let distance = levenshtein_distance(&name, &query);
+                (contains_query || distance <= max_distance)
+                    .then(|| ((!contains_query, distance, entry.id()), entry))
+            })
+            .collect();
+        matches.sort_by_key(|(rank, _)| *rank);
+        matches.into_iter().map(|(_, entry)| entry).collect()
+    }
+
+    /// Find the entries whose name or description contains the query, ignoring case, e.g. for a search box.
+    /// Entries whose name matches come before those only matching by description, otherwise entries are in the same order as [iter](Self::iter).
+    /// A blank query matches no entries.
+    pub fn search(&self, query: &str) -> Vec<EntryResponse> {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let (mut name_matches, description_matches): (Vec<_>, Vec<_>) = self
+            .iter()
+            .filter_map(|entry| {
+                let name_matches = entry.name().to_lowercase().contains(&query);
+                (name_matches || entry.description().to_lowercase().contains(&query))
+                    .then_some((name_matches, entry))
+            })
+            .partition(|(name_matches, _)| *name_matches);
+        name_matches.extend(description_matches);
+        name_matches.into_iter().map(|(_, entry)| entry).collect()
+    }
+
+    /// Every creature, monster and treasure entry that drops the item (ignoring case), e.g. to find every source of amber.
+    /// Entries are in the same order as [iter](Self::iter).
+    pub fn entries_dropping(&self, item: &str) -> Vec<EntryResponse> {
+        let item = normalize_drop(item);
+        self.iter()
+            .filter(|entry| {
+                entry
+                    .drops()
+                    .is_some_and(|drops| drops.iter().any(|drop| normalize_drop(drop) == item))
+            })
+            .collect()
+    }
+
+    /// Find the entry whose image url exactly matches the given url
+    pub fn find_by_image(&self, image_url: &str) -> Option<&dyn CompendiumEntry> {
+        self.entries().find(|entry| entry.image() == image_url)
+    }
+
+    /// A deterministic hash of every entry from the compendium, which changes whenever any entry is added, removed or modified.
+    ///
+    /// Each entry is serialized to JSON, the entries are sorted by category type then id and the hash is the 64-bit
+    /// [FNV-1a](http://www.isthe.com/chongo/tech/comp/fnv/index.html) hash of those serialized entries separated by a newline.
+    /// The order the API returns entries in doesn't affect the hash.
+    pub fn content_hash(&self) -> u64 {
+        let mut serialized_entries: Vec<(&str, i32, String)> = Vec::new();
+        #[cfg(feature = "creatures")]
+        {
+            serialized_entries.extend(serialize_entries(&self.creatures.food));
+            serialized_entries.extend(serialize_entries(&self.creatures.non_food));
+        }
+        #[cfg(feature = "equipment")]
+        serialized_entries.extend(serialize_entries(&self.equipment));
+        #[cfg(feature = "materials")]
+        serialized_entries.extend(serialize_entries(&self.materials));
+        #[cfg(feature = "monsters")]
+        serialized_entries.extend(serialize_entries(&self.monsters));
+        #[cfg(feature = "treasure")]
+        serialized_entries.extend(serialize_entries(&self.treasure));
+        serialized_entries.sort();
+        serialized_entries
+            .iter()
+            .fold(FNV_OFFSET_BASIS, |hash, (_, _, serialized)| {
+                fnv1a(fnv1a(hash, serialized.as_bytes()), b"\n")
+            })
+    }
+
+    /// Count the entries in each category, with creatures counting both food and non-food
+    pub fn category_counts(&self) -> CategoryCounts {
+        let counts = CompendiumCategory::ALL
+            .iter()
+            .map(|category| (*category, self.category_entries(*category).count()))
+            .collect();
+        CategoryCounts { counts }
+    }
+
+    /// A copy of every entry grouped by its [category_type](CompendiumEntry::category_type), e.g. for a faceted browse UI.
+    /// The keys are the API's plural category names (e.g. "monsters" or "creatures"), with food and non-food creatures grouped together,
+    /// and each group's entries are in the same order as [iter](Self::iter). Categories without any entries have no key.
+    pub fn group_by_category_type(&self) -> HashMap<String, Vec<EntryResponse>> {
+        let mut groups: HashMap<String, Vec<EntryResponse>> = HashMap::new();
+        for entry in self.iter() {
+            groups
+                .entry(entry.category_type().to_string())
+                .or_default()
+                .push(entry);
+        }
+        groups
+    }
+
+    /// Compare these entries with another set of entries, such as those from a mirror of the API, reporting how each category has drifted.
+    /// Entries are matched by id, so an entry is added when only `other` has it, removed when only these entries have it
+    /// and changed when both have it but their serialized JSON differs.
+    pub fn drift(&self, other: &AllStandardEntries) -> DatasetDrift {
+        let categories = CompendiumCategory::ALL
+            .iter()
+            .map(|category| {
+                let entries = self.serialized_category(*category);
+                let other_entries = other.serialized_category(*category);
+                let category_drift = CategoryDrift {
+                    added: other_entries
+                        .keys()
+                        .filter(|id| !entries.contains_key(id))
+                        .count(),
+                    removed: entries
+                        .keys()
+                        .filter(|id| !other_entries.contains_key(id))
+                        .count(),
+                    changed: entries
+                        .iter()
+                        .filter(|(id, serialized)| {
+                            other_entries
+                                .get(id)
+                                .is_some_and(|other_serialized| other_serialized != *serialized)
+                        })
+                        .count(),
+                };
+                (*category, category_drift)
+            })
+            .collect();
+        DatasetDrift {
+            categories,
+            content_hash: self.content_hash(),
+            other_content_hash: other.content_hash(),
+        }
+    }
+
+    /// Deterministically select an entry for the given day, so everyone asking on the same day gets the same entry.
+    /// The day is a plain seed, such as the number of days since the unix epoch, which avoids depending on a date library.
+    /// Entries are ordered by id (then category type) and the entry at the day modulo the number of entries is selected,
+    /// so consecutive days rotate through every entry. Returns `None` when there are no entries.
+    pub fn entry_of_the_day(&self, day: u64) -> Option<&dyn CompendiumEntry> {
+        let mut entries: Vec<&dyn CompendiumEntry> = self.entries().collect();
+        if entries.is_empty() {
+            return None;
+        }
+        entries.sort_by(|a, b| {
+            a.id()
+                .cmp(&b.id())
+                .then_with(|| a.category_type().cmp(b.category_type()))
+        });
+        let index = (day % entries.len() as u64) as usize;
+        Some(entries[index])
+    }
+
+    /// Iterate over a copy of every entry from the compendium, each wrapped in the [EntryResponse] for its category.
+    /// Entries are grouped by category: creatures (food then non-food), equipment, materials, monsters then treasure,
+    /// and within each category they're in the order the API returned them.
+    pub fn iter(&self) -> impl Iterator<Item = EntryResponse> + '_ {
+        let entries = std::iter::empty();
+        #[cfg(feature = "creatures")]
+        let entries = entries.chain(
+            self.creatures
+                .food
+                .iter()
+                .chain(&self.creatures.non_food)
+                .cloned()
+                .map(EntryResponse::Creature),
+        );
+        #[cfg(feature = "equipment")]
+        let entries = entries.chain(self.equipment.iter().cloned().map(EntryResponse::Equipment));
+        #[cfg(feature = "materials")]
+        let entries = entries.chain(self.materials.iter().cloned().map(EntryResponse::Material));
+        #[cfg(feature = "monsters")]
+        let entries = entries.chain(self.monsters.iter().cloned().map(EntryResponse::Monster));
+        #[cfg(feature = "treasure")]
+        let entries = entries.chain(self.treasure.iter().cloned().map(EntryResponse::Treasure));
+        entries
+    }
+
+    /// A copy of every entry from the compendium in a single vec, each wrapped in the [EntryResponse] for its category, in the same order as [iter](Self::iter)
+    pub fn all(&self) -> Vec<EntryResponse> {
+        self.iter().collect()
+    }
+
+    /// Every entry from the compendium in a single vec, each wrapped in the [EntryResponse] for its category, in the same order as [iter](Self::iter).
+    /// Unlike [all](Self::all) this consumes the entries, so none are copied.
+    pub fn into_all(self) -> Vec<EntryResponse> {
+        let mut entries = Vec::new();
+        #[cfg(feature = "creatures")]
+        entries.extend(
+            self.creatures
+                .food
+                .into_iter()
+                .chain(self.creatures.non_food)
+                .map(EntryResponse::Creature),
+        );
+        #[cfg(feature = "equipment")]
+        entries.extend(self.equipment.into_iter().map(EntryResponse::Equipment));
+        #[cfg(feature = "materials")]
+        entries.extend(self.materials.into_iter().map(EntryResponse::Material));
+        #[cfg(feature = "monsters")]
+        entries.extend(self.monsters.into_iter().map(EntryResponse::Monster));
+        #[cfg(feature = "treasure")]
+        entries.extend(self.treasure.into_iter().map(EntryResponse::Treasure));
+        entries
+    }
+
+    /// Replace the entries of a category with the entries from a category result
+    pub(crate) fn replace_category(&mut self, category_result: CategoryResult) {
+        match category_result {
+            #[cfg(feature = "creatures")]
+            CategoryResult::Creatures(creatures) => self.creatures = creatures,
+            #[cfg(feature = "equipment")]
+            CategoryResult::Equipment(equipment) => self.equipment = equipment,
+            #[cfg(feature = "materials")]
+            CategoryResult::Materials(materials) => self.materials = materials,
+            #[cfg(feature = "monsters")]
+            CategoryResult::Monsters(monsters) => self.monsters = monsters,
+            #[cfg(feature = "treasure")]
+            CategoryResult::Treasure(treasure) => self.treasure = treasure,
+        }
+    }
+
+    /// Every entry from the compendium in the given category serialized to JSON, keyed by id
+    fn serialized_category(&self, category: CompendiumCategory) -> HashMap<i32, String> {
+        let serialized_entries: Vec<(&str, i32, String)> = match category {
+            #[cfg(feature = "creatures")]
+            CompendiumCategory::Creature => serialize_entries(&self.creatures.food)
+                .chain(serialize_entries(&self.creatures.non_food))
+                .collect(),
+            #[cfg(feature = "equipment")]
+            CompendiumCategory::Equipment => serialize_entries(&self.equipment).collect(),
+            #[cfg(feature = "materials")]
+            CompendiumCategory::Material => serialize_entries(&self.materials).collect(),
+            #[cfg(feature = "monsters")]
+            CompendiumCategory::Monster => serialize_entries(&self.monsters).collect(),
+            #[cfg(feature = "treasure")]
+            CompendiumCategory::Treasure => serialize_entries(&self.treasure).collect(),
+        };
+        serialized_entries
+            .into_iter()
+            .map(|(_, id, serialized)| (id, serialized))
+            .collect()
+    }
+
+    /// Every entry from the compendium in the given category
+    pub(crate) fn category_entries(
+        &self,
+        category: CompendiumCategory,
+    ) -> Box<dyn Iterator<Item = &dyn CompendiumEntry> + '_> {
+        match category {
+            #[cfg(feature = "creatures")]
+            CompendiumCategory::Creature => Box::new(self.creatures.entries()),
+            #[cfg(feature = "equipment")]
+            CompendiumCategory::Equipment => Box::new(self.equipment.iter().map(as_entry)),
+            #[cfg(feature = "materials")]
+            CompendiumCategory::Material => Box::new(self.materials.iter().map(as_entry)),
+            #[cfg(feature = "monsters")]
+            CompendiumCategory::Monster => Box::new(self.monsters.iter().map(as_entry)),
+            #[cfg(feature = "treasure")]
+            CompendiumCategory::Treasure => Box::new(self.treasure.iter().map(as_entry)),
+        }
+    }
+
+    /// Every entry from the compendium, grouped by category (creatures, equipment, materials, monsters then treasure)
+    pub(crate) fn entries(&self) -> impl Iterator<Item = &dyn CompendiumEntry> {
+        let entries = std::iter::empty();
+        #[cfg(feature = "creatures")]
+        let entries = entries.chain(self.creatures.entries());
+        #[cfg(feature = "equipment")]
+        let entries = entries.chain(self.equipment.iter().map(as_entry));
+        #[cfg(feature = "materials")]
+        let entries = entries.chain(self.materials.iter().map(as_entry));
+        #[cfg(feature = "monsters")]
+        let entries = entries.chain(self.monsters.iter().map(as_entry));
+        #[cfg(feature = "treasure")]
+        let entries = entries.chain(self.treasure.iter().map(as_entry));
+        entries
+    }
+}
+
+/// The number of entries in each category of the compendium, see [category_counts](AllStandardEntries::category_counts)
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CategoryCounts {
+    counts: HashMap<CompendiumCategory, usize>,
+}
+
+impl CategoryCounts {
+    /// Get the number of entries in the category
+    pub fn count(&self, category: CompendiumCategory) -> usize {
+        self.counts.get(&category).copied().unwrap_or_default()
+    }
+
+    /// Get the number of entries in each category
+    pub fn counts(&self) -> &HashMap<CompendiumCategory, usize> {
+        &self.counts
+    }
+
+    /// Get the number of entries across every category
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+}
+
+/// How one set of entries from the compendium has drifted from another, see [drift](AllStandardEntries::drift)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DatasetDrift {
+    categories: HashMap<CompendiumCategory, CategoryDrift>,
+    content_hash: u64,
+    other_content_hash: u64,
+}
+
+impl DatasetDrift {
+    /// Get how each category has drifted
+    pub fn categories(&self) -> &HashMap<CompendiumCategory, CategoryDrift> {
+        &self.categories
+    }
+
+    /// Get the [content hash](AllStandardEntries::content_hash) of the entries being compared against
+    pub fn content_hash(&self) -> u64 {
+        self.content_hash
+    }
+
+    /// Get the [content hash](AllStandardEntries::content_hash) of the other entries
+    pub fn other_content_hash(&self) -> u64 {
+        self.other_content_hash
+    }
+
+    /// Whether the entries differ at all, i.e. their content hashes don't match
+    pub fn has_drifted(&self) -> bool {
+        self.content_hash != self.other_content_hash
+    }
+}
+
+/// How the entries in one category have drifted, see [DatasetDrift]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CategoryDrift {
+    added: usize,
+    removed: usize,
+    changed: usize,
+}
+
+impl CategoryDrift {
+    /// Get the number of entries only in the other entries
+    pub fn added(&self) -> usize {
+        self.added
+    }
+
+    /// Get the number of entries missing from the other entries
+    pub fn removed(&self) -> usize {
+        self.removed
+    }
+
+    /// Get the number of entries in both whose contents differ
+    pub fn changed(&self) -> usize {
+        self.changed
+    }
+}
+
+fn upsert_entry<T: CompendiumEntry>(entries: &mut Vec<T>, entry: T) -> bool {
+    match entries
+        .iter_mut()
+        .find(|existing| existing.id() == entry.id())
+    {
+        Some(existing) => {
+            *existing = entry;
+            true
+        }
+        None => {
+            entries.push(entry);
+            false
+        }
+    }
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+fn fnv1a(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+fn serialize_entries<T: CompendiumEntry + Serialize>(
+    entries: &[T],
+) -> impl Iterator<Item = (&str, i32, String)> {
+    entries.iter().map(|entry| {
+        (
+            entry.category_type(),
+            entry.id(),
+            serde_json::to_string(entry).expect("entries only contain data representable as JSON"),
+        )
+    })
+}
+
+/// The minimum number of single character insertions, deletions or substitutions to change one string into the other
+fn levenshtein_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous_row: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut row = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous_row[j] + usize::from(a_char != *b_char);
+            row.push(substitution.min(previous_row[j + 1] + 1).min(row[j] + 1));
+        }
+        previous_row = row;
+    }
+    previous_row[b.len()]
+}
+
+fn as_entry<T: CompendiumEntry>(entry: &T) -> &dyn CompendiumEntry {
+    entry
 }
 
 /// A representation of possible responses from the compendium API.
-#[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(tag = "category")]
+/// Entries are told apart by their `category`, an entry in a category this crate doesn't know about is kept as [Unknown](EntryResponse::Unknown).
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum EntryResponse {
     /// The entry obtained was of the monster category
-    #[serde(rename = "monsters")]
+    #[cfg(feature = "monsters")]
     Monster(MonsterEntry),
     /// The entry obtained was of the creature category
-    #[serde(rename = "creatures")]
+    #[cfg(feature = "creatures")]
     Creature(CreatureEntry),
     /// The entry obtained was of the equipment category
-    #[serde(rename = "equipment")]
+    #[cfg(feature = "equipment")]
     Equipment(EquipmentEntry),
     /// The entry obtained was of the treasure category
-    #[serde(rename = "treasure")]
+    #[cfg(feature = "treasure")]
     Treasure(TreasureEntry),
     /// The entry obtained was of the material category
-    #[serde(rename = "materials")]
+    #[cfg(feature = "materials")]
     Material(MaterialEntry),
+    /// The entry obtained was of a category this crate doesn't know about, e.g. one added to the API since this crate was released,
+    /// or one whose feature is disabled. The entry's raw JSON is preserved as is, including its `category`, so no data is lost.
+    /// The [CompendiumEntry] accessors read the shared fields (e.g. `id` and `name`) from the raw JSON, defaulting when they're missing.
+    Unknown {
+        /// The category of the entry, as named by the API
+        category: String,
+        /// The entry's JSON as returned by the API
+        raw: serde_json::Value,
+    },
+}
+
+/// The [EntryResponse] variants as they're serialized, tagged with their category
+#[derive(Serialize)]
+#[serde(tag = "category")]
+enum TaggedEntry<'a> {
+    #[cfg(feature = "monsters")]
+    #[serde(rename = "monsters")]
+    Monster(&'a MonsterEntry),
+    #[cfg(feature = "creatures")]
+    #[serde(rename = "creatures")]
+    Creature(&'a CreatureEntry),
+    #[cfg(feature = "equipment")]
+    #[serde(rename = "equipment")]
+    Equipment(&'a EquipmentEntry),
+    #[cfg(feature = "treasure")]
+    #[serde(rename = "treasure")]
+    Treasure(&'a TreasureEntry),
+    #[cfg(feature = "materials")]
+    #[serde(rename = "materials")]
+    Material(&'a MaterialEntry),
+}
+
+impl Serialize for EntryResponse {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let tagged = match self {
+            #[cfg(feature = "monsters")]
+            EntryResponse::Monster(entry) => TaggedEntry::Monster(entry),
+            #[cfg(feature = "creatures")]
+            EntryResponse::Creature(entry) => TaggedEntry::Creature(entry),
+            #[cfg(feature = "equipment")]
+            EntryResponse::Equipment(entry) => TaggedEntry::Equipment(entry),
+            #[cfg(feature = "treasure")]
+            EntryResponse::Treasure(entry) => TaggedEntry::Treasure(entry),
+            #[cfg(feature = "materials")]
+            EntryResponse::Material(entry) => TaggedEntry::Material(entry),
+            EntryResponse::Unknown { raw, .. } => return raw.serialize(serializer),
+        };
+        tagged.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for EntryResponse {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let raw = serde_json::Value::deserialize(deserializer)?;
+        let category = raw
+            .get("category")
+            .and_then(serde_json::Value::as_str)
+            .ok_or_else(|| D::Error::missing_field("category"))?
+            .to_string();
+        let entry = match category.as_str() {
+            #[cfg(feature = "monsters")]
+            "monsters" => untagged_entry(raw).map(EntryResponse::Monster),
+            #[cfg(feature = "creatures")]
+            "creatures" => untagged_entry(raw).map(EntryResponse::Creature),
+            #[cfg(feature = "equipment")]
+            "equipment" => untagged_entry(raw).map(EntryResponse::Equipment),
+            #[cfg(feature = "treasure")]
+            "treasure" => untagged_entry(raw).map(EntryResponse::Treasure),
+            #[cfg(feature = "materials")]
+            "materials" => untagged_entry(raw).map(EntryResponse::Material),
+            _ => return Ok(EntryResponse::Unknown { category, raw }),
+        };
+        entry.map_err(D::Error::custom)
+    }
+}
+
+/// Deserialize an entry without its `category` tag, as the tag is only used to tell the categories apart
+fn untagged_entry<T: DeserializeOwned>(mut raw: serde_json::Value) -> serde_json::Result<T> {
+    if let serde_json::Value::Object(fields) = &mut raw {
+        fields.remove("category");
+    }
+    serde_json::from_value(raw)
+}
+
+impl EntryResponse {
+    /// Load an entry from JSON in the shape the API returns it, i.e. wrapped in `{"data": ...}`, such as a saved response from `entry/<id>`.
+    /// Invalid JSON results in a [DeserializationError](crate::CompendiumError::DeserializationError).
+    pub fn from_json_str(json: &str) -> Result<Self> {
+        data_from_json_str(json)
+    }
+
+    /// The entry as its category's model, which an [Unknown](EntryResponse::Unknown) entry doesn't have
+    fn inner(&self) -> Option<&dyn CompendiumEntry> {
+        match self {
+            #[cfg(feature = "monsters")]
+            EntryResponse::Monster(entry) => Some(entry),
+            #[cfg(feature = "creatures")]
+            EntryResponse::Creature(entry) => Some(entry),
+            #[cfg(feature = "equipment")]
+            EntryResponse::Equipment(entry) => Some(entry),
+            #[cfg(feature = "treasure")]
+            EntryResponse::Treasure(entry) => Some(entry),
+            #[cfg(feature = "materials")]
+            EntryResponse::Material(entry) => Some(entry),
+            EntryResponse::Unknown { .. } => None,
+        }
+    }
+
+    /// A field of an [Unknown](EntryResponse::Unknown) entry's raw JSON
+    fn raw_field(&self, field: &str) -> Option<&serde_json::Value> {
+        match self {
+            EntryResponse::Unknown { raw, .. } => raw.get(field),
+            _ => None,
+        }
+    }
+
+    /// A string field of an [Unknown](EntryResponse::Unknown) entry's raw JSON, empty when it's missing
+    fn raw_str(&self, field: &str) -> &str {
+        self.raw_field(field)
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default()
+    }
+
+    /// Convert the entry into a monster entry, for when the category is already known.
+    /// Any other entry results in a [UnexpectedCategory](crate::CompendiumError::UnexpectedCategory).
+    #[cfg(feature = "monsters")]
+    pub fn into_monster(self) -> Result<MonsterEntry> {
+        match self {
+            EntryResponse::Monster(entry) => Ok(entry),
+            other => Err(other.unexpected_category("monsters")),
+        }
+    }
+
+    /// Convert the entry into a creature entry, for when the category is already known.
+    /// Any other entry results in a [UnexpectedCategory](crate::CompendiumError::UnexpectedCategory).
+    #[cfg(feature = "creatures")]
+    pub fn into_creature(self) -> Result<CreatureEntry> {
+        match self {
+            EntryResponse::Creature(entry) => Ok(entry),
+            other => Err(other.unexpected_category("creatures")),
+        }
+    }
+
+    /// Convert the entry into an equipment entry, for when the category is already known.
+    /// Any other entry results in a [UnexpectedCategory](crate::CompendiumError::UnexpectedCategory).
+    #[cfg(feature = "equipment")]
+    pub fn into_equipment(self) -> Result<EquipmentEntry> {
+        match self {
+            EntryResponse::Equipment(entry) => Ok(entry),
+            other => Err(other.unexpected_category("equipment")),
+        }
+    }
+
+    /// Convert the entry into a treasure entry, for when the category is already known.
+    /// Any other entry results in a [UnexpectedCategory](crate::CompendiumError::UnexpectedCategory).
+    #[cfg(feature = "treasure")]
+    pub fn into_treasure(self) -> Result<TreasureEntry> {
+        match self {
+            EntryResponse::Treasure(entry) => Ok(entry),
+            other => Err(other.unexpected_category("treasure")),
+        }
+    }
+
+    /// Convert the entry into a material entry, for when the category is already known.
+    /// Any other entry results in a [UnexpectedCategory](crate::CompendiumError::UnexpectedCategory).
+    #[cfg(feature = "materials")]
+    pub fn into_material(self) -> Result<MaterialEntry> {
+        match self {
+            EntryResponse::Material(entry) => Ok(entry),
+            other => Err(other.unexpected_category("materials")),
+        }
+    }
+
+    /// The error for converting the entry into the model of another category
+    fn unexpected_category(&self, expected: &str) -> CompendiumError {
+        CompendiumError::UnexpectedCategory {
+            expected: expected.to_string(),
+            found: self.category_type().to_string(),
+        }
+    }
+
+    /// Serialize the entry in the shape the compendium API responds with, i.e. wrapped in a `data` envelope
+    /// and tagged with its `category`. Useful for generating fixtures for a mock of the API.
+    pub fn to_api_json(&self) -> serde_json::Value {
+        let mut entry =
+            serde_json::to_value(self).expect("entries only contain data representable as JSON");
+        if let serde_json::Value::Object(fields) = &mut entry {
+            fields.remove("category_type");
+        }
+        serde_json::json!({ "data": entry })
+    }
+}
+
+/// Fails with a [UnexpectedCategory](crate::CompendiumError::UnexpectedCategory) for any other entry, see [into_monster](EntryResponse::into_monster)
+#[cfg(feature = "monsters")]
+impl TryFrom<EntryResponse> for MonsterEntry {
+    type Error = CompendiumError;
+
+    fn try_from(entry: EntryResponse) -> Result<Self> {
+        entry.into_monster()
+    }
+}
+
+/// Fails with a [UnexpectedCategory](crate::CompendiumError::UnexpectedCategory) for any other entry, see [into_creature](EntryResponse::into_creature)
+#[cfg(feature = "creatures")]
+impl TryFrom<EntryResponse> for CreatureEntry {
+    type Error = CompendiumError;
+
+    fn try_from(entry: EntryResponse) -> Result<Self> {
+        entry.into_creature()
+    }
+}
+
+/// Fails with a [UnexpectedCategory](crate::CompendiumError::UnexpectedCategory) for any other entry, see [into_equipment](EntryResponse::into_equipment)
+#[cfg(feature = "equipment")]
+impl TryFrom<EntryResponse> for EquipmentEntry {
+    type Error = CompendiumError;
+
+    fn try_from(entry: EntryResponse) -> Result<Self> {
+        entry.into_equipment()
+    }
+}
+
+/// Fails with a [UnexpectedCategory](crate::CompendiumError::UnexpectedCategory) for any other entry, see [into_treasure](EntryResponse::into_treasure)
+#[cfg(feature = "treasure")]
+impl TryFrom<EntryResponse> for TreasureEntry {
+    type Error = CompendiumError;
+
+    fn try_from(entry: EntryResponse) -> Result<Self> {
+        entry.into_treasure()
+    }
+}
+
+/// Fails with a [UnexpectedCategory](crate::CompendiumError::UnexpectedCategory) for any other entry, see [into_material](EntryResponse::into_material)
+#[cfg(feature = "materials")]
+impl TryFrom<EntryResponse> for MaterialEntry {
+    type Error = CompendiumError;
+
+    fn try_from(entry: EntryResponse) -> Result<Self> {
+        entry.into_material()
+    }
+}
+
+/// Equipment and material entries never have drops, and the drops of an [Unknown](EntryResponse::Unknown) entry aren't read
+impl HasDrops for EntryResponse {
+    fn drops(&self) -> Option<&Vec<String>> {
+        match self {
+            #[cfg(feature = "monsters")]
+            EntryResponse::Monster(monster) => monster.drops(),
+            #[cfg(feature = "creatures")]
+            EntryResponse::Creature(creature) => creature.drops(),
+            #[cfg(feature = "treasure")]
+            EntryResponse::Treasure(treasure) => treasure.drops(),
+            _ => None,
+        }
+    }
+}
+
+/// An [Unknown](EntryResponse::Unknown) entry reads its fields from its raw JSON, except for its common locations which are always `None`
+impl CompendiumEntry for EntryResponse {
+    fn id(&self) -> i32 {
+        match self.inner() {
+            Some(entry) => entry.id(),
+            None => self
+                .raw_field("id")
+                .and_then(serde_json::Value::as_i64)
+                .and_then(|id| i32::try_from(id).ok())
+                .unwrap_or_default(),
+        }
+    }
+
+    fn name(&self) -> &str {
+        self.inner()
+            .map_or_else(|| self.raw_str("name"), |entry| entry.name())
+    }
+
+    fn description(&self) -> &str {
+        self.inner()
+            .map_or_else(|| self.raw_str("description"), |entry| entry.description())
+    }
+
+    fn common_locations(&self) -> Option<&Vec<String>> {
+        self.inner().and_then(|entry| entry.common_locations())
+    }
+
+    fn image(&self) -> &str {
+        self.inner()
+            .map_or_else(|| self.raw_str("image"), |entry| entry.image())
+    }
+
+    fn dlc(&self) -> Option<bool> {
+        match self.inner() {
+            Some(entry) => entry.dlc(),
+            None => self.raw_field("dlc").and_then(serde_json::Value::as_bool),
+        }
+    }
+
+    fn category_type(&self) -> &str {
+        match self {
+            EntryResponse::Unknown { category, .. } => category,
+            _ => self.inner().map_or("", |entry| entry.category_type()),
+        }
+    }
+
+    fn to_markdown(&self) -> String {
+        match self.inner() {
+            Some(entry) => entry.to_markdown(),
+            None => render_markdown(self, &[], None),
+        }
+    }
 }
 
 /// A representation of all creatures that can be returned from the compendium API
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[cfg(feature = "creatures")]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct AllCreatureEntries {
+    #[serde(deserialize_with = "crate::api::deserialize_one_or_many")]
     food: Vec<CreatureEntry>,
+    #[serde(deserialize_with = "crate::api::deserialize_one_or_many")]
     non_food: Vec<CreatureEntry>,
 }
 
+#[cfg(feature = "creatures")]
 impl AllCreatureEntries {
+    /// Every creature entry, food then non-food
+    pub(crate) fn entries(&self) -> impl Iterator<Item = &dyn CompendiumEntry> {
+        self.food.iter().chain(self.non_food.iter()).map(as_entry)
+    }
+
     /// Get a reference to the creature food entries
     pub fn food(&self) -> &Vec<CreatureEntry> {
         &self.food
@@ -113,20 +1153,882 @@ impl AllCreatureEntries {
     pub fn non_food_mut(&mut self) -> &mut Vec<CreatureEntry> {
         &mut self.non_food
     }
+
+    /// Get the creature entries whose cooking effect matches any of the given effects, ignoring case.
+    /// An empty slice of effects matches no creatures.
+    pub fn with_any_effect(&self, effects: &[&str]) -> Vec<&CreatureEntry> {
+        self.filter_by_effect(|effect| {
+            effects
+                .iter()
+                .any(|expected| effect.eq_ignore_ascii_case(expected))
+        })
+    }
+
+    /// Get the creature entries whose cooking effect helps with the given gameplay situation, e.g. [Cold](crate::domain::inputs::Situation::Cold) for creatures with "cold resistance".
+    /// See [effect](crate::domain::inputs::Situation::effect) for the effect each situation maps to.
+    pub fn for_situation(&self, situation: Situation) -> Vec<&CreatureEntry> {
+        self.with_any_effect(&[situation.effect()])
+    }
+
+    /// Get the creature entries whose cooking effect matches all of the given effects, ignoring case.
+    /// As a creature has a single cooking effect, this only matches when the given effects are the same.
+    /// An empty slice of effects matches every creature with a cooking effect.
+    pub fn with_all_effects(&self, effects: &[&str]) -> Vec<&CreatureEntry> {
+        self.filter_by_effect(|effect| {
+            effects
+                .iter()
+                .all(|expected| effect.eq_ignore_ascii_case(expected))
+        })
+    }
+
+    /// For each of the given cooking effects, whether at least one creature food entry provides it (ignoring case).
+    /// The keys of the returned map are the effects exactly as given.
+    pub fn effect_coverage(&self, all_effects: &[&str]) -> HashMap<String, bool> {
+        all_effects
+            .iter()
+            .map(|expected| {
+                let covered = self.food.iter().any(|creature| {
+                    creature
+                        .cooking_effect()
+                        .is_some_and(|effect| effect.eq_ignore_ascii_case(expected))
+                });
+                (expected.to_string(), covered)
+            })
+            .collect()
+    }
+
+    fn filter_by_effect<F: Fn(&str) -> bool>(&self, predicate: F) -> Vec<&CreatureEntry> {
+        self.food
+            .iter()
+            .chain(self.non_food.iter())
+            .filter(|creature| {
+                creature
+                    .cooking_effect()
+                    .is_some_and(|effect| predicate(effect))
+            })
+            .collect()
+    }
+
+    /// Get the creature food entries that have at least one common location
+    pub fn foragable_food(&self) -> Vec<&CreatureEntry> {
+        self.food
+            .iter()
+            .filter(|creature| {
+                creature
+                    .common_locations()
+                    .is_some_and(|locations| !locations.is_empty())
+            })
+            .collect()
+    }
 }
 
 /// An enum containing the possible responses while retrieving a category.
 /// In the case where the search is for the treasure category, it should be expected that the treasure variant of this enum will be returned.
-#[derive(Debug, Clone)]
+///
+/// It's serialized with the category's name as the API names it alongside the entries, e.g. `{"category":"monsters","data":[...]}`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "category", content = "data")]
 pub enum CategoryResult {
     /// All entries from the treasure category
+    #[cfg(feature = "treasure")]
+    #[serde(rename = "treasure")]
     Treasure(Vec<TreasureEntry>),
     /// All entries from the creature category
+    #[cfg(feature = "creatures")]
+    #[serde(rename = "creatures")]
     Creatures(AllCreatureEntries),
     /// All entries from the monster category
+    #[cfg(feature = "monsters")]
+    #[serde(rename = "monsters")]
     Monsters(Vec<MonsterEntry>),
     /// All entries from the material category
+    #[cfg(feature = "materials")]
+    #[serde(rename = "materials")]
     Materials(Vec<MaterialEntry>),
     /// All entries from the equipment category
+    #[cfg(feature = "equipment")]
+    #[serde(rename = "equipment")]
     Equipment(Vec<EquipmentEntry>),
 }
+
+impl CategoryResult {
+    /// Every entry within the category result
+    pub(crate) fn entries(&self) -> Box<dyn Iterator<Item = &dyn CompendiumEntry> + '_> {
+        match self {
+            #[cfg(feature = "treasure")]
+            CategoryResult::Treasure(treasure) => Box::new(treasure.iter().map(as_entry)),
+            #[cfg(feature = "creatures")]
+            CategoryResult::Creatures(creatures) => Box::new(creatures.entries()),
+            #[cfg(feature = "monsters")]
+            CategoryResult::Monsters(monsters) => Box::new(monsters.iter().map(as_entry)),
+            #[cfg(feature = "materials")]
+            CategoryResult::Materials(materials) => Box::new(materials.iter().map(as_entry)),
+            #[cfg(feature = "equipment")]
+            CategoryResult::Equipment(equipment) => Box::new(equipment.iter().map(as_entry)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn all_entries_data<'a>() -> &'a str {
+        r#"{"creatures":{"food":[{"category":"creatures","common_locations":["Hyrule Ridge","Tabantha Frontier"],"cooking_effect":"heat resistance","description":"The powdery scales of this butterfly's wings cool the air around it.","hearts_recovered":0,"id":67,"image":"https://botw-compendium.herokuapp.com/api/v2/entry/winterwing_butterfly/image","name":"winterwing butterfly"},{"category":"creatures","common_locations":null,"cooking_effect":"","description":"This fish lives in rivers and lakes all over Hyrule.","hearts_recovered":1,"id":50,"image":"https://botw-compendium.herokuapp.com/api/v2/entry/hyrule_bass/image","name":"hyrule bass"}],"non_food":[{"category":"creatures","common_locations":["Hyrule Field","Gerudo Highlands"],"description":"Horses are a means of transportation in Hyrule.","drops":["AMBER"],"id":1,"image":"https://botw-compendium.herokuapp.com/api/v2/entry/horse/image","name":"horse"}]},"equipment":[{"attack":30,"category":"equipment","common_locations":["Hyrule Castle"],"defense":null,"description":"The legendary sword that seals the darkness.","id":347,"image":"https://botw-compendium.herokuapp.com/api/v2/entry/master_sword/image","name":"master sword"}],"materials":[{"category":"materials","common_locations":["Great Hyrule Forest"],"cooking_effect":"","description":"This fruit is said to be common in Hyrule.","hearts_recovered":0.5,"id":183,"image":"https://botw-compendium.herokuapp.com/api/v2/entry/apple/image","name":"apple"}],"monsters":[{"category":"monsters","common_locations":null,"description":"The strongest of all Moblins.","drops":["moblin horn","moblin fang","amber"],"id":112,"image":"https://botw-compendium.herokuapp.com/api/v2/entry/silver_moblin/image","name":"silver moblin"},{"category":"monsters","common_locations":["Hyrule Field"],"description":"This enormous monster blends in perfectly with its rocky surroundings.","drops":[],"id":124,"image":"https://botw-compendium.herokuapp.com/api/v2/entry/stone_talus/image","name":"stone talus"}],"treasure":[{"category":"treasure","common_locations":["Hyrule Field"],"description":"This treasure chest contains rupees.","drops":["rupee","Amber"],"id":385,"image":"https://botw-compendium.herokuapp.com/api/v2/entry/treasure_chest/image","name":"treasure chest"}]}"#
+    }
+
+    fn all_entries() -> AllStandardEntries {
+        serde_json::from_str(all_entries_data()).unwrap()
+    }
+
+    #[test]
+    fn test_all_standard_entries_summaries() {
+        let summaries = all_entries().summaries();
+        let ids: Vec<i32> = summaries.iter().map(EntrySummary::id).collect();
+        assert_eq!(vec![67, 50, 1, 347, 183, 112, 124, 385], ids);
+        assert_eq!("treasure", summaries.last().unwrap().category());
+    }
+
+    #[test]
+    fn test_entry_markdown() {
+        let entries = all_entries();
+        assert_eq!(
+            "## silver moblin\n\n![silver moblin](https://botw-compendium.herokuapp.com/api/v2/entry/silver_moblin/image)\n\nThe strongest of all Moblins.\n\n- **Id:** 112\n- **Category:** monsters\n\n### Drops\n\n- moblin horn\n- moblin fang\n- amber\n",
+            entries.monsters()[0].to_markdown()
+        );
+        assert_eq!(
+            "## master sword\n\n![master sword](https://botw-compendium.herokuapp.com/api/v2/entry/master_sword/image)\n\nThe legendary sword that seals the darkness.\n\n- **Id:** 347\n- **Category:** equipment\n- **Attack:** 30\n\n### Common locations\n\n- Hyrule Castle\n",
+            EntryResponse::Equipment(entries.equipment()[0].clone()).to_markdown()
+        );
+    }
+
+    #[test]
+    fn test_all_creature_entries_foragable_food() {
+        let entries = all_entries();
+        let foragable: Vec<i32> = entries
+            .creatures()
+            .foragable_food()
+            .iter()
+            .map(|creature| creature.id())
+            .collect();
+        assert_eq!(vec![67], foragable);
+    }
+
+    #[test]
+    fn test_all_creature_entries_with_any_effect() {
+        let entries = all_entries();
+        let creatures = entries.creatures();
+        let matching = creatures.with_any_effect(&["Heat Resistance", "cold resistance"]);
+        assert_eq!(1, matching.len());
+        assert_eq!(67, matching[0].id());
+        assert!(creatures.with_any_effect(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_all_creature_entries_for_situation() {
+        let entries = all_entries();
+        let creatures = entries.creatures();
+        let matching = creatures.for_situation(Situation::Heat);
+        assert_eq!(1, matching.len());
+        assert_eq!(67, matching[0].id());
+        assert!(creatures.for_situation(Situation::Cold).is_empty());
+    }
+
+    #[test]
+    fn test_all_creature_entries_with_all_effects() {
+        let entries = all_entries();
+        let creatures = entries.creatures();
+        assert_eq!(1, creatures.with_all_effects(&["heat resistance"]).len());
+        assert!(creatures
+            .with_all_effects(&["heat resistance", "cold resistance"])
+            .is_empty());
+    }
+
+    #[test]
+    fn test_all_standard_entries_find_by_image() {
+        let entries = all_entries();
+        let entry = entries
+            .find_by_image("https://botw-compendium.herokuapp.com/api/v2/entry/apple/image")
+            .unwrap();
+        assert_eq!(183, entry.id());
+        assert!(entries
+            .find_by_image("https://botw-compendium.herokuapp.com/api/v2/entry/Apple/image")
+            .is_none());
+    }
+
+    #[test]
+    fn test_all_standard_entries_content_hash() {
+        let entries = all_entries();
+        assert_eq!(entries.content_hash(), all_entries().content_hash());
+
+        let mut reordered = all_entries();
+        reordered.creatures_mut().food_mut().reverse();
+        assert_eq!(entries.content_hash(), reordered.content_hash());
+
+        let mut modified = all_entries();
+        modified.monsters_mut().pop();
+        assert_ne!(entries.content_hash(), modified.content_hash());
+    }
+
+    #[test]
+    fn test_all_creature_entries_effect_coverage() {
+        let entries = all_entries();
+        let coverage = entries
+            .creatures()
+            .effect_coverage(&["Heat Resistance", "cold resistance"]);
+        assert_eq!(2, coverage.len());
+        assert!(coverage["Heat Resistance"]);
+        assert!(!coverage["cold resistance"]);
+    }
+
+    #[test]
+    fn test_all_standard_entries_without_drops() {
+        let entries = all_entries();
+        let monsters: Vec<i32> = entries
+            .monsters_without_drops()
+            .iter()
+            .map(|monster| monster.id())
+            .collect();
+        assert_eq!(vec![124], monsters);
+        assert!(entries.treasure_without_drops().is_empty());
+    }
+
+    #[test]
+    fn test_all_standard_entries_all_locations() {
+        let locations: Vec<String> = all_entries().all_locations().into_iter().collect();
+        assert_eq!(
+            vec![
+                "Gerudo Highlands",
+                "Great Hyrule Forest",
+                "Hyrule Castle",
+                "Hyrule Field",
+                "Hyrule Ridge",
+                "Tabantha Frontier"
+            ],
+            locations
+        );
+    }
+
+    #[test]
+    fn test_all_standard_entries_insert() {
+        let mut entries = all_entries();
+        let monster = entries.monsters()[0].clone();
+        assert!(entries.insert(EntryResponse::Monster(monster)));
+        assert_eq!(2, entries.monsters().len());
+
+        let horse = entries.creatures().non_food()[0].clone();
+        let mut other_entries = all_entries();
+        other_entries.creatures_mut().non_food_mut().clear();
+        assert!(!other_entries.insert(EntryResponse::Creature(horse)));
+        assert_eq!(1, other_entries.creatures().non_food().len());
+        assert_eq!(2, other_entries.creatures().food().len());
+    }
+
+    #[test]
+    fn test_entry_response_to_api_json() {
+        let monster = EntryResponse::Monster(all_entries().monsters()[0].clone());
+        let json = monster.to_api_json();
+        assert_eq!(
+            serde_json::json!({"data": {"category":"monsters","common_locations":null,"description":"The strongest of all Moblins.","drops":["moblin horn","moblin fang","amber"],"id":112,"image":"https://botw-compendium.herokuapp.com/api/v2/entry/silver_moblin/image","name":"silver moblin"}}),
+            json
+        );
+
+        let parsed: crate::api::ApiResponse<EntryResponse> = serde_json::from_value(json).unwrap();
+        assert!(matches!(parsed.data, EntryResponse::Monster(_)));
+        assert_eq!(112, parsed.data.id());
+        assert_eq!("monsters", parsed.data.category_type());
+    }
+
+    #[test]
+    fn test_all_standard_entries_descriptions_outside() {
+        let entries = all_entries();
+        let ids: Vec<i32> = entries
+            .descriptions_outside(30, 68)
+            .iter()
+            .map(|entry| entry.id())
+            .collect();
+        assert_eq!(vec![112, 124], ids);
+        assert!(entries.descriptions_outside(0, usize::MAX).is_empty());
+    }
+
+    #[test]
+    fn test_all_standard_entries_non_ascii_names() {
+        let mut entries = all_entries();
+        assert!(entries.non_ascii_names().is_empty());
+        let accented_entry: MonsterEntry = serde_json::from_str(
+            r#"{"category":"monsters","common_locations":null,"description":"A test entry.","drops":[],"id":999,"image":"","name":"pokémon"}"#,
+        )
+        .unwrap();
+        entries.monsters_mut().push(accented_entry);
+        let ids: Vec<i32> = entries
+            .non_ascii_names()
+            .iter()
+            .map(|entry| entry.id())
+            .collect();
+        assert_eq!(vec![999], ids);
+    }
+
+    #[test]
+    fn test_all_standard_entries_drift() {
+        let entries = all_entries();
+        let mut other_entries = all_entries();
+        other_entries.monsters_mut().remove(0);
+        other_entries.treasure_mut()[0] = serde_json::from_str(
+            r#"{"category":"treasure","common_locations":["Hyrule Field"],"description":"This treasure chest contains rupees.","drops":["rupee"],"id":385,"image":"https://botw-compendium.herokuapp.com/api/v2/entry/treasure_chest/image","name":"treasure chest"}"#,
+        )
+        .unwrap();
+        let drift = entries.drift(&other_entries);
+        assert!(drift.has_drifted());
+        assert_eq!(
+            (0, 1, 0),
+            drift_counts(drift.categories()[&CompendiumCategory::Monster])
+        );
+        assert_eq!(
+            (0, 0, 1),
+            drift_counts(drift.categories()[&CompendiumCategory::Treasure])
+        );
+        assert_eq!(
+            CategoryDrift::default(),
+            drift.categories()[&CompendiumCategory::Creature]
+        );
+
+        let reverse_drift = other_entries.drift(&entries);
+        assert_eq!(
+            (1, 0, 0),
+            drift_counts(reverse_drift.categories()[&CompendiumCategory::Monster])
+        );
+        assert!(!entries.drift(&all_entries()).has_drifted());
+    }
+
+    fn drift_counts(category_drift: CategoryDrift) -> (usize, usize, usize) {
+        (
+            category_drift.added(),
+            category_drift.removed(),
+            category_drift.changed(),
+        )
+    }
+
+    #[test]
+    fn test_all_standard_entries_entries_dropping() {
+        let entries = all_entries();
+        let amber_sources = entries.entries_dropping("Amber");
+        assert_eq!(3, amber_sources.len());
+        assert!(matches!(amber_sources[0], EntryResponse::Creature(_)));
+        assert!(matches!(amber_sources[1], EntryResponse::Monster(_)));
+        assert!(matches!(amber_sources[2], EntryResponse::Treasure(_)));
+        let drop_index = entries.drop_index();
+        let index_ids: Vec<i32> = drop_index["amber"].iter().map(|entry| entry.id()).collect();
+        let dropping_ids: Vec<i32> = amber_sources.iter().map(EntryResponse::id).collect();
+        assert_eq!(index_ids, dropping_ids);
+        assert_eq!(1, entries.entries_dropping("moblin fang").len());
+        assert!(entries.entries_dropping("master sword").is_empty());
+    }
+
+    #[test]
+    fn test_levenshtein_distance() {
+        assert_eq!(0, levenshtein_distance("moblin", "moblin"));
+        assert_eq!(1, levenshtein_distance("moblin", "mobin"));
+        assert_eq!(3, levenshtein_distance("kitten", "sitting"));
+        assert_eq!(5, levenshtein_distance("", "horse"));
+    }
+
+    #[test]
+    fn test_all_standard_entries_find_by_name_fuzzy() {
+        let entries = all_entries();
+        let ids = |query: &str, max_distance: usize| -> Vec<i32> {
+            entries
+                .find_by_name_fuzzy(query, max_distance)
+                .iter()
+                .map(|entry| entry.id())
+                .collect()
+        };
+        assert_eq!(vec![112], ids("Silver Mobin", 2));
+        assert_eq!(vec![112], ids("moblin", 0));
+        assert_eq!(vec![1], ids("hors", 1));
+        assert_eq!(vec![1, 183], ids("horse", 4));
+        assert!(ids("lynel", 1).is_empty());
+    }
+
+    #[test]
+    fn test_all_standard_entries_from_json_str() {
+        let entries =
+            AllStandardEntries::from_json_str(&format!(r#"{{"data":{}}}"#, all_entries_data()))
+                .unwrap();
+        assert_eq!(2, entries.monsters().len());
+        assert!(matches!(
+            AllStandardEntries::from_json_str(all_entries_data()),
+            Err(CompendiumError::DeserializationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_entry_response_from_json_str() {
+        let entry = EntryResponse::from_json_str(
+            r#"{"data":{"category":"monsters","common_locations":null,"description":"The strongest of all Moblins.","drops":["moblin horn"],"id":112,"image":"https://botw-compendium.herokuapp.com/api/v2/entry/silver_moblin/image","name":"silver moblin"}}"#,
+        )
+        .unwrap();
+        assert!(matches!(entry, EntryResponse::Monster(_)));
+        assert_eq!(112, entry.id());
+        assert!(matches!(
+            EntryResponse::from_json_str("not json"),
+            Err(CompendiumError::DeserializationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_entry_response_into_entry() {
+        let entries = all_entries();
+        let monster = EntryResponse::Monster(entries.monsters()[0].clone());
+        assert_eq!(112, monster.clone().into_monster().unwrap().id());
+        let monster: MonsterEntry = monster.try_into().unwrap();
+        assert_eq!(112, monster.id());
+        let equipment = EntryResponse::Equipment(entries.equipment()[0].clone());
+        assert_eq!(347, EquipmentEntry::try_from(equipment).unwrap().id());
+    }
+
+    #[test]
+    fn test_entry_response_into_entry_unexpected_category() {
+        let treasure = EntryResponse::Treasure(all_entries().treasure()[0].clone());
+        match treasure.into_monster() {
+            Err(CompendiumError::UnexpectedCategory { expected, found }) => {
+                assert_eq!("monsters", expected);
+                assert_eq!("treasure", found);
+            }
+            result => panic!("Expected an unexpected category, got {:?}", result),
+        }
+        let unknown = EntryResponse::Unknown {
+            category: String::from("characters"),
+            raw: serde_json::json!({"category": "characters"}),
+        };
+        assert!(matches!(
+            CreatureEntry::try_from(unknown),
+            Err(CompendiumError::UnexpectedCategory { found, .. }) if found == "characters"
+        ));
+    }
+
+    #[test]
+    fn test_entry_response_unknown_category() {
+        let json = r#"{"data":{"category":"characters","description":"The princess of Hyrule.","id":400,"image":"","name":"zelda","title":"princess"}}"#;
+        let entry = EntryResponse::from_json_str(json).unwrap();
+        match &entry {
+            EntryResponse::Unknown { category, raw } => {
+                assert_eq!("characters", category);
+                assert_eq!("princess", raw["title"]);
+            }
+            _ => panic!("expected an unknown entry"),
+        }
+        assert_eq!(400, entry.id());
+        assert_eq!("zelda", entry.name());
+        assert_eq!("The princess of Hyrule.", entry.description());
+        assert_eq!("characters", entry.category_type());
+        assert_eq!(None, entry.common_locations());
+        assert_eq!(None, entry.dlc());
+        assert_eq!(None, entry.drops());
+
+        let round_tripped: EntryResponse =
+            serde_json::from_value(serde_json::to_value(&entry).unwrap()).unwrap();
+        assert_eq!(entry, round_tripped);
+    }
+
+    #[test]
+    fn test_entry_response_without_category() {
+        assert!(matches!(
+            EntryResponse::from_json_str(r#"{"data":{"id":112,"name":"silver moblin"}}"#),
+            Err(CompendiumError::DeserializationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_entry_response_serde_round_trip() {
+        for entry in all_entries().iter() {
+            let json = serde_json::to_value(&entry).unwrap();
+            assert_eq!(entry.category_type(), json["category"]);
+            let round_tripped: EntryResponse = serde_json::from_value(json).unwrap();
+            assert_eq!(entry, round_tripped);
+        }
+    }
+
+    #[test]
+    fn test_category_result_serde_round_trip() {
+        let entries = all_entries();
+        let monsters = CategoryResult::Monsters(entries.monsters().clone());
+        let json = serde_json::to_value(&monsters).unwrap();
+        assert_eq!("monsters", json["category"]);
+        assert_eq!(112, json["data"][0]["id"]);
+        match serde_json::from_value(json).unwrap() {
+            CategoryResult::Monsters(round_tripped) => assert_eq!(2, round_tripped.len()),
+            _ => panic!("expected monsters"),
+        }
+
+        let creatures = CategoryResult::Creatures(entries.creatures().clone());
+        let json = serde_json::to_string(&creatures).unwrap();
+        match serde_json::from_str(&json).unwrap() {
+            CategoryResult::Creatures(round_tripped) => {
+                assert_eq!(2, round_tripped.food().len());
+                assert_eq!(1, round_tripped.non_food().len());
+            }
+            _ => panic!("expected creatures"),
+        }
+    }
+
+    #[test]
+    fn test_all_standard_entries_iter() {
+        let entries = all_entries();
+        let ids: Vec<i32> = entries.iter().map(|entry| entry.id()).collect();
+        assert_eq!(vec![67, 50, 1, 347, 183, 112, 124, 385], ids);
+        assert!(matches!(
+            entries.iter().nth(3),
+            Some(EntryResponse::Equipment(_))
+        ));
+        assert!(matches!(
+            entries.iter().last(),
+            Some(EntryResponse::Treasure(_))
+        ));
+    }
+
+    #[test]
+    fn test_all_standard_entries_category_counts() {
+        let counts = all_entries().category_counts();
+        assert_eq!(3, counts.count(CompendiumCategory::Creature));
+        assert_eq!(1, counts.count(CompendiumCategory::Equipment));
+        assert_eq!(1, counts.count(CompendiumCategory::Material));
+        assert_eq!(2, counts.count(CompendiumCategory::Monster));
+        assert_eq!(1, counts.count(CompendiumCategory::Treasure));
+        assert_eq!(8, counts.total());
+        assert_eq!(0, AllStandardEntries::default().category_counts().total());
+    }
+
+    #[test]
+    fn test_all_standard_entries_group_by_category_type() {
+        let groups = all_entries().group_by_category_type();
+        let ids = |category_type: &str| -> Vec<i32> {
+            groups[category_type]
+                .iter()
+                .map(|entry| entry.id())
+                .collect()
+        };
+        let mut keys: Vec<&str> = groups.keys().map(String::as_str).collect();
+        keys.sort_unstable();
+        assert_eq!(
+            vec![
+                "creatures",
+                "equipment",
+                "materials",
+                "monsters",
+                "treasure"
+            ],
+            keys
+        );
+        assert_eq!(vec![67, 50, 1], ids("creatures"));
+        assert_eq!(vec![112, 124], ids("monsters"));
+        assert_eq!(vec![385], ids("treasure"));
+        assert!(groups["monsters"]
+            .iter()
+            .all(|entry| matches!(entry, EntryResponse::Monster(_))));
+        assert!(AllStandardEntries::default()
+            .group_by_category_type()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_all_standard_entries_search_ranks_name_matches_first() {
+        let entries = all_entries();
+        let ids = |query: &str| -> Vec<i32> {
+            entries
+                .search(query)
+                .iter()
+                .map(|entry| entry.id())
+                .collect()
+        };
+        // "moblin" is in silver moblin's name and description
+        assert_eq!(vec![112], ids("MOBLIN"));
+        // "hyrule" is in hyrule bass's name and description, but only in the descriptions of the horse and apple
+        assert_eq!(vec![50, 1, 183], ids("hyrule"));
+        assert_eq!(vec![385], ids(" rupees "));
+        assert!(ids("lynel").is_empty());
+        assert!(ids("  ").is_empty());
+    }
+
+    #[test]
+    fn test_all_standard_entries_all() {
+        let entries = all_entries();
+        let all = entries.all();
+        assert_eq!(8, all.len());
+        let ids: Vec<i32> = all.iter().map(|entry| entry.id()).collect();
+        assert_eq!(vec![67, 50, 1, 347, 183, 112, 124, 385], ids);
+        let owned_ids: Vec<i32> = entries.into_all().iter().map(|entry| entry.id()).collect();
+        assert_eq!(ids, owned_ids);
+    }
+
+    #[test]
+    fn test_entry_image_url() {
+        let entries = all_entries();
+        let image_url = entries.monsters()[0].image_url().unwrap();
+        assert_eq!(
+            "botw-compendium.herokuapp.com",
+            image_url.host_str().unwrap()
+        );
+        assert_eq!("/api/v2/entry/silver_moblin/image", image_url.path());
+    }
+
+    #[test]
+    fn test_entries_display_summary() {
+        let entries = all_entries();
+        assert_eq!(
+            "winterwing butterfly (#67, creatures)\nThe powdery scales of this butterfly's wings cool the air around it.\nHearts recovered: 0\nCooking effect: heat resistance\nCommon locations: Hyrule Ridge, Tabantha Frontier",
+            entries.creatures().food()[0].to_string()
+        );
+        assert_eq!(
+            "master sword (#347, equipment)\nThe legendary sword that seals the darkness.\nAttack: 30\nCommon locations: Hyrule Castle",
+            entries.equipment()[0].to_string()
+        );
+        assert_eq!(
+            "apple (#183, materials)\nThis fruit is said to be common in Hyrule.\nHearts recovered: 0.5\nCommon locations: Great Hyrule Forest",
+            entries.materials()[0].to_string()
+        );
+        assert_eq!(
+            "silver moblin (#112, monsters)\nThe strongest of all Moblins.\nDrops: moblin horn, moblin fang, amber",
+            entries.monsters()[0].to_string()
+        );
+        assert_eq!(
+            "stone talus (#124, monsters)\nThis enormous monster blends in perfectly with its rocky surroundings.\nCommon locations: Hyrule Field",
+            entries.monsters()[1].to_string()
+        );
+        assert_eq!(
+            "treasure chest (#385, treasure)\nThis treasure chest contains rupees.\nCommon locations: Hyrule Field\nDrops: rupee, Amber",
+            entries.treasure()[0].to_string()
+        );
+    }
+
+    #[test]
+    fn test_entry_responses_dedupe_by_equality() {
+        let entries = all_entries();
+        let unique: HashSet<EntryResponse> = entries.iter().chain(entries.iter()).collect();
+        assert_eq!(8, unique.len());
+        assert!(unique.contains(&entries.all()[5]));
+    }
+
+    #[test]
+    fn test_all_standard_entries_into_all() {
+        let all = all_entries().into_all();
+        let count = |matches: fn(&EntryResponse) -> bool| all.iter().filter(|e| matches(e)).count();
+        assert_eq!(
+            3,
+            count(|entry| matches!(entry, EntryResponse::Creature(_)))
+        );
+        assert_eq!(
+            1,
+            count(|entry| matches!(entry, EntryResponse::Equipment(_)))
+        );
+        assert_eq!(
+            1,
+            count(|entry| matches!(entry, EntryResponse::Material(_)))
+        );
+        assert_eq!(2, count(|entry| matches!(entry, EntryResponse::Monster(_))));
+        assert_eq!(
+            1,
+            count(|entry| matches!(entry, EntryResponse::Treasure(_)))
+        );
+    }
+
+    #[test]
+    fn test_all_standard_entries_contains() {
+        let entries = all_entries();
+        assert!(entries.contains(EntryIdentifier::Id(112)));
+        assert!(!entries.contains(EntryIdentifier::Id(999)));
+        assert!(entries.contains(EntryIdentifier::Name("silver moblin")));
+        assert!(entries.contains(EntryIdentifier::Name("Silver_Moblin")));
+        assert!(!entries.contains(EntryIdentifier::Name("golden moblin")));
+    }
+
+    #[test]
+    fn test_all_standard_entries_entry_of_the_day() {
+        let entries = all_entries();
+        assert_eq!(1, entries.entry_of_the_day(0).unwrap().id());
+        assert_eq!(50, entries.entry_of_the_day(1).unwrap().id());
+        assert_eq!(385, entries.entry_of_the_day(7).unwrap().id());
+        assert_eq!(1, entries.entry_of_the_day(8).unwrap().id());
+        assert_eq!(
+            entries.entry_of_the_day(20_000).unwrap().id(),
+            all_entries().entry_of_the_day(20_000).unwrap().id()
+        );
+
+        let mut empty = all_entries();
+        empty.creatures_mut().food_mut().clear();
+        empty.creatures_mut().non_food_mut().clear();
+        empty.equipment_mut().clear();
+        empty.materials_mut().clear();
+        empty.monsters_mut().clear();
+        empty.treasure_mut().clear();
+        assert!(empty.entry_of_the_day(0).is_none());
+    }
+
+    #[test]
+    fn test_all_standard_entries_partition_dlc() {
+        let mut entries = all_entries();
+        let (base_game, dlc) = entries.partition_dlc();
+        assert_eq!(8, base_game.len());
+        assert!(dlc.is_empty());
+
+        let dlc_monster: MonsterEntry = serde_json::from_value(serde_json::json!({
+            "category": "monsters",
+            "common_locations": null,
+            "description": "",
+            "dlc": true,
+            "drops": null,
+            "id": 400,
+            "image": "",
+            "name": "monster control crew"
+        }))
+        .unwrap();
+        entries.insert(EntryResponse::Monster(dlc_monster));
+        let (base_game, dlc) = entries.partition_dlc();
+        assert_eq!(8, base_game.len());
+        assert_eq!(
+            vec![400],
+            dlc.iter().map(|entry| entry.id()).collect::<Vec<_>>()
+        );
+    }
+
+    #[cfg(feature = "indexmap")]
+    #[test]
+    fn test_all_standard_entries_index_by_name_ordered() {
+        let mut entries = all_entries();
+        let same_name = entries.monsters()[0].clone();
+        let differently_cased_name: MonsterEntry = serde_json::from_value(serde_json::json!({
+            "category": "monsters",
+            "common_locations": null,
+            "description": "",
+            "drops": null,
+            "id": 999,
+            "image": "",
+            "name": "Silver Moblin"
+        }))
+        .unwrap();
+        entries.monsters_mut().push(same_name);
+        entries.monsters_mut().push(differently_cased_name);
+        let index = entries.index_by_name_ordered();
+        let names: Vec<&str> = index.keys().map(String::as_str).collect();
+        assert_eq!(
+            vec![
+                "winterwing butterfly",
+                "hyrule bass",
+                "horse",
+                "master sword",
+                "apple",
+                "silver moblin",
+                "stone talus",
+                "treasure chest"
+            ],
+            names
+        );
+        assert_eq!(112, index["silver moblin"].id());
+    }
+
+    #[test]
+    fn test_all_standard_entries_retain_categories() {
+        let entries = all_entries()
+            .retain_categories(&[CompendiumCategory::Monster, CompendiumCategory::Creature]);
+        assert_eq!(2, entries.monsters().len());
+        assert_eq!(2, entries.creatures().food().len());
+        assert_eq!(1, entries.creatures().non_food().len());
+        assert!(entries.equipment().is_empty());
+        assert!(entries.materials().is_empty());
+        assert!(entries.treasure().is_empty());
+
+        assert_eq!(0, all_entries().retain_categories(&[]).entries().count());
+    }
+
+    #[test]
+    fn test_all_standard_entries_counts_by_location() {
+        let counts = all_entries().counts_by_location();
+        assert_eq!(all_entries().all_locations().len(), counts.len());
+        assert_eq!(3, counts["Hyrule Field"]);
+        assert_eq!(1, counts["Hyrule Castle"]);
+        assert_eq!(1, counts["Tabantha Frontier"]);
+    }
+
+    #[test]
+    fn test_all_standard_entries_location_cooccurrence() {
+        let mut entries = all_entries();
+        let monster: MonsterEntry = serde_json::from_value(serde_json::json!({
+            "category": "monsters",
+            "common_locations": ["Tabantha Frontier", "Hyrule Ridge", "Hyrule Ridge", "Hebra Mountains"],
+            "description": "",
+            "drops": null,
+            "id": 400,
+            "image": "",
+            "name": "ice-breath lizalfos"
+        }))
+        .unwrap();
+        entries.insert(EntryResponse::Monster(monster));
+        let cooccurrence = entries.location_cooccurrence();
+        let pair = |first: &str, second: &str| (first.to_string(), second.to_string());
+        assert_eq!(4, cooccurrence.len());
+        assert_eq!(2, cooccurrence[&pair("Hyrule Ridge", "Tabantha Frontier")]);
+        assert_eq!(1, cooccurrence[&pair("Hebra Mountains", "Hyrule Ridge")]);
+        assert_eq!(
+            1,
+            cooccurrence[&pair("Hebra Mountains", "Tabantha Frontier")]
+        );
+        assert_eq!(1, cooccurrence[&pair("Gerudo Highlands", "Hyrule Field")]);
+        assert!(!cooccurrence.contains_key(&pair("Hyrule Ridge", "Hyrule Ridge")));
+    }
+
+    #[test]
+    fn test_all_standard_entries_image_coverage() {
+        assert_eq!(1.0, all_entries().image_coverage());
+
+        let without_master_sword_image: AllStandardEntries =
+            serde_json::from_str(&all_entries_data().replace(
+                "https://botw-compendium.herokuapp.com/api/v2/entry/master_sword/image",
+                "",
+            ))
+            .unwrap();
+        let total = without_master_sword_image.entries().count();
+        assert_eq!(8, total);
+        assert_eq!(
+            (total - 1) as f32 / total as f32,
+            without_master_sword_image.image_coverage()
+        );
+
+        let mut entries = all_entries();
+        entries.creatures_mut().food_mut().clear();
+        entries.creatures_mut().non_food_mut().clear();
+        entries.equipment_mut().clear();
+        entries.materials_mut().clear();
+        entries.monsters_mut().clear();
+        entries.treasure_mut().clear();
+        assert_eq!(0.0, entries.image_coverage());
+    }
+
+    #[test]
+    fn test_all_standard_entries_breakdown_table() {
+        assert_eq!(
+            "Category    | Count
+------------+-------
+Creatures   |     3
+  Food      |     2
+  Non-food  |     1
+Equipment   |     1
+Materials   |     1
+Monsters    |     2
+Treasure    |     1
+------------+-------
+Total       |     8
+",
+            all_entries().breakdown_table()
+        );
+    }
+
+    #[test]
+    fn test_all_standard_entries_drop_index() {
+        let entries = all_entries();
+        let index = entries.drop_index();
+        let amber_ids: Vec<i32> = index["amber"].iter().map(|entry| entry.id()).collect();
+        assert_eq!(vec![1, 112, 385], amber_ids);
+        assert_eq!(1, index["rupee"].len());
+        assert!(!index.contains_key("Amber"));
+    }
+}
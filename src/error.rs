@@ -7,19 +7,111 @@ pub enum CompendiumError {
     /// An invalid base url exists for the Compendium client
     #[error("Invalid base url of '{0}' provided")]
     InvalidBaseUrl(String),
+    /// An error representing a failure in configuring the underlying HTTP client
+    #[error("An error occurred while configuring the HTTP client")]
+    ClientConfigurationError(#[source] reqwest::Error),
     /// An error representing a failure in building the url to request the resource's data
     #[error("An error occurred while trying to create the resource path")]
     ErrorConstructingResourceUrl,
-    /// An error representing a failure in requesting the data
+    /// An error representing a failure in requesting the data, other than failing to connect or timing out
     #[error("An error in occurred while requesting data")]
     RequestError(#[source] reqwest::Error),
+    /// An error representing a failure in connecting to the API, e.g. the host couldn't be resolved or refused the connection,
+    /// or the connection failing while a response that's deserialized as it's read was being received
+    #[error("An error occurred while connecting to the API")]
+    ConnectionError(#[source] reqwest::Error),
+    /// An error representing a request that didn't complete within the configured timeout
+    #[error("The request timed out")]
+    Timeout(#[source] reqwest::Error),
     /// An error representing no data found for the requested resource
     #[error("There was no data found for '{0}'")]
     NoDataFound(String),
+    /// An error representing the API rejecting the request with a client error status other than not found (e.g. 400 or 403)
+    #[error("The request for '{path}' was rejected with status {status}")]
+    ClientError {
+        /// The HTTP status code of the response
+        status: u16,
+        /// The path of the resource that was requested
+        path: String,
+    },
     /// An error representing a failure in the API's response
     #[error("There was an unexpected error from the server")]
     ServerError,
-    /// An error representing a failure in parsing the API's response
+    /// An error representing a failure in parsing the API's response once it's been received in full.
+    /// Responses the blocking client deserializes as they're read (categories and all entries) fail with a [DeserializationError](Self::DeserializationError) instead
     #[error("There was an error in parsing the response")]
     ResponseParsingError(#[source] reqwest::Error),
+    /// An error representing a failure in deserializing data into the expected type,
+    /// including the category and all entries responses the blocking client deserializes as they're read
+    #[error("There was an error in deserializing the data")]
+    DeserializationError(#[source] serde_json::Error),
+    /// An error representing a paginated response that didn't finish within the maximum number of pages
+    #[error("The response was not complete after following {0} pages")]
+    PageLimitExceeded(usize),
+    /// An error representing more than one entry with the same id being returned
+    #[error("More than one entry was found with the id '{0}'")]
+    DuplicateId(i32),
+    /// An error representing an entry whose category doesn't match the category that was requested
+    #[error("Expected an entry in the '{expected}' category but found one in '{found}'")]
+    CategoryMismatch {
+        /// The category that was requested
+        expected: String,
+        /// The category of the entry that was returned
+        found: String,
+    },
+    /// An error representing an entry converted into the model of a category it isn't in, e.g. a treasure entry into a monster.
+    /// Unlike a [CategoryMismatch](Self::CategoryMismatch) this comes from converting an entry locally, not from the API's response
+    #[error("Expected an entry in the '{expected}' category to convert but it was in '{found}'")]
+    UnexpectedCategory {
+        /// The category of the model the entry was converted into
+        expected: String,
+        /// The category of the entry
+        found: String,
+    },
+    /// An error representing a category name that doesn't match any compendium category
+    #[error("'{0}' is not a compendium category, expected one of creatures, equipment, materials, monsters or treasure")]
+    UnknownCategory(String),
+    /// An error representing a failure in reading or writing a file, or in reading a response for a reason other than the connection failing
+    #[error("An error occurred while accessing a file")]
+    IoError(#[source] std::io::Error),
+    /// An error representing a failure in decoding an entry's image
+    #[cfg(feature = "image")]
+    #[error("There was an error in decoding the image")]
+    ImageDecodeError(#[source] image::ImageError),
+    /// An error representing a web compendium page that doesn't have the expected structure, e.g. because the page has changed
+    #[cfg(feature = "scraping")]
+    #[error("The page didn't have the expected structure, {0}")]
+    UnexpectedHtml(String),
+}
+
+impl CompendiumError {
+    /// Classify an error from sending a request, a timeout takes precedence over a connection error as connecting can time out
+    pub(crate) fn from_request_error(error: reqwest::Error) -> CompendiumError {
+        if error.is_timeout() {
+            CompendiumError::Timeout(error)
+        } else if error.is_connect() {
+            CompendiumError::ConnectionError(error)
+        } else {
+            CompendiumError::RequestError(error)
+        }
+    }
+
+    /// Classify an error from deserializing a response as it's read, separating failures to read the body
+    /// (e.g. a timeout or the connection being reset) from invalid data, so the former can be retried like any other transport error
+    pub(crate) fn from_streamed_body_error(error: serde_json::Error) -> CompendiumError {
+        if !error.is_io() {
+            return CompendiumError::DeserializationError(error);
+        }
+        let io_error = std::io::Error::from(error);
+        let kind = io_error.kind();
+        match io_error
+            .into_inner()
+            .map(|inner| inner.downcast::<reqwest::Error>())
+        {
+            Some(Ok(error)) if error.is_timeout() => CompendiumError::Timeout(*error),
+            Some(Ok(error)) => CompendiumError::ConnectionError(*error),
+            Some(Err(inner)) => CompendiumError::IoError(std::io::Error::new(kind, inner)),
+            None => CompendiumError::IoError(std::io::Error::from(kind)),
+        }
+    }
 }
@@ -0,0 +1,94 @@
+//! A flat representation of entries for passing across a C FFI boundary
+#![allow(unsafe_code)]
+use std::ffi::{c_char, CStr, CString};
+
+/// A `repr(C)` representation of an entry's id, name and image, see [to_c_entry](crate::domain::models::CompendiumEntry::to_c_entry).
+///
+/// The `name` and `image` pointers are NUL terminated strings allocated by this crate and owned by the `CEntry`.
+/// They stay valid for as long as the `CEntry` is alive and are freed when it's dropped, so they must not be freed by C
+/// and must not be used after the `CEntry` is dropped. To hand a `CEntry` to C for longer, wrap it in
+/// [ManuallyDrop](std::mem::ManuallyDrop) (or box it) and return it to Rust to be dropped once C is finished with it.
+/// As C strings can't contain NUL characters, any in the name or image are removed.
+#[repr(C)]
+#[derive(Debug)]
+pub struct CEntry {
+    /// The entry's id
+    pub id: i32,
+    /// The entry's name as a NUL terminated string, owned by the `CEntry`
+    pub name: *mut c_char,
+    /// The entry's image url as a NUL terminated string, owned by the `CEntry`
+    pub image: *mut c_char,
+}
+
+impl CEntry {
+    pub(crate) fn new(id: i32, name: &str, image: &str) -> Self {
+        CEntry {
+            id,
+            name: into_c_string(name),
+            image: into_c_string(image),
+        }
+    }
+
+    /// Get the entry's name
+    pub fn name(&self) -> &CStr {
+        // Safety: the pointer was created by `CString::into_raw` and is only freed when the entry is dropped
+        unsafe { CStr::from_ptr(self.name) }
+    }
+
+    /// Get the entry's image url
+    pub fn image(&self) -> &CStr {
+        // Safety: the pointer was created by `CString::into_raw` and is only freed when the entry is dropped
+        unsafe { CStr::from_ptr(self.image) }
+    }
+}
+
+impl Drop for CEntry {
+    fn drop(&mut self) {
+        // Safety: both pointers were created by `CString::into_raw` and ownership was never given away
+        unsafe {
+            drop(CString::from_raw(self.name));
+            drop(CString::from_raw(self.image));
+        }
+    }
+}
+
+fn into_c_string(value: &str) -> *mut c_char {
+    CString::new(value.replace('\0', ""))
+        .unwrap_or_default()
+        .into_raw()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_c_entry() {
+        let entry = CEntry::new(112, "silver\0 moblin", "https://example.com/image");
+        assert_eq!(112, entry.id);
+        assert_eq!(c"silver moblin", entry.name());
+        assert_eq!(c"https://example.com/image", entry.image());
+    }
+
+    #[test]
+    fn test_to_c_entry() {
+        use crate::domain::models::{CompendiumEntry, MonsterEntry};
+        let monster: MonsterEntry = serde_json::from_value(serde_json::json!({
+            "category": "monsters",
+            "common_locations": null,
+            "description": "",
+            "drops": null,
+            "id": 112,
+            "image": "https://botw-compendium.herokuapp.com/api/v2/entry/silver_moblin/image",
+            "name": "silver moblin"
+        }))
+        .unwrap();
+        let entry = monster.to_c_entry();
+        assert_eq!(112, entry.id);
+        assert_eq!(c"silver moblin", entry.name());
+        assert_eq!(
+            c"https://botw-compendium.herokuapp.com/api/v2/entry/silver_moblin/image",
+            entry.image()
+        );
+    }
+}
@@ -0,0 +1,114 @@
+//! Records metrics about the requests made to the API through the [metrics](https://docs.rs/metrics) facade,
+//! and traces them through [tracing](https://docs.rs/tracing).
+//!
+//! Every span and event is emitted with the `rusty_hyrule_compendium::request` target:
+//! - a `compendium_request` span around each request (including its retries), with the requested `url`
+//! - a debug event as each request is sent, with its `url`
+//! - a debug event when a request succeeds, with its `status` and `duration_ms`
+//! - a warn event when a request fails, with the `error`, its `kind` and `duration_ms`
+use crate::error::CompendiumError;
+use crate::result::Result;
+#[cfg(feature = "tracing")]
+use reqwest::Url;
+use std::time::Instant;
+
+/// The total number of requests made to the API
+#[cfg(feature = "metrics")]
+const REQUESTS_TOTAL: &str = "compendium_requests_total";
+/// The total number of failed requests, labelled by the kind of error
+#[cfg(feature = "metrics")]
+const ERRORS_TOTAL: &str = "compendium_errors_total";
+/// How long requests to the API took, in seconds
+#[cfg(feature = "metrics")]
+const REQUEST_DURATION_SECONDS: &str = "compendium_request_duration_seconds";
+
+/// Record a request to the API that started at the given instant
+#[cfg(feature = "metrics")]
+pub(crate) fn record_request<T>(started: Instant, result: &Result<T>) {
+    metrics::counter!(REQUESTS_TOTAL).increment(1);
+    metrics::histogram!(REQUEST_DURATION_SECONDS).record(started.elapsed().as_secs_f64());
+    if let Err(error) = result {
+        metrics::counter!(ERRORS_TOTAL, "kind" => error_kind(error)).increment(1);
+    }
+}
+
+/// The span covering a request to the url, including any retries
+#[cfg(feature = "tracing")]
+pub(crate) fn request_span(url: &Url) -> tracing::Span {
+    tracing::debug_span!(target: "rusty_hyrule_compendium::request", "compendium_request", url = %url)
+}
+
+/// Trace a request to the url as it's sent
+#[cfg(feature = "tracing")]
+pub(crate) fn trace_request_sent(url: &Url) {
+    tracing::debug!(target: "rusty_hyrule_compendium::request", url = %url, "sending request");
+}
+
+/// Trace the outcome of a request that started at the given instant, with the status of a successful response
+#[cfg(feature = "tracing")]
+pub(crate) fn trace_response<T>(started: Instant, result: &Result<T>, status: fn(&T) -> u16) {
+    let duration_ms = started.elapsed().as_millis();
+    match result {
+        Ok(response) => tracing::debug!(
+            target: "rusty_hyrule_compendium::request",
+            status = status(response),
+            duration_ms,
+            "request succeeded"
+        ),
+        Err(error) => tracing::warn!(
+            target: "rusty_hyrule_compendium::request",
+            error = %error,
+            kind = error_kind(error),
+            duration_ms,
+            "request failed"
+        ),
+    }
+}
+
+fn error_kind(error: &CompendiumError) -> &'static str {
+    match error {
+        CompendiumError::InvalidBaseUrl(_) => "invalid_base_url",
+        CompendiumError::ClientConfigurationError(_) => "client_configuration",
+        CompendiumError::ErrorConstructingResourceUrl => "resource_url",
+        CompendiumError::RequestError(_) => "request",
+        CompendiumError::ConnectionError(_) => "connection",
+        CompendiumError::Timeout(_) => "timeout",
+        CompendiumError::NoDataFound(_) => "no_data_found",
+        CompendiumError::ClientError { .. } => "client",
+        CompendiumError::ServerError => "server",
+        CompendiumError::ResponseParsingError(_) => "response_parsing",
+        CompendiumError::DeserializationError(_) => "deserialization",
+        CompendiumError::PageLimitExceeded(_) => "page_limit_exceeded",
+        CompendiumError::DuplicateId(_) => "duplicate_id",
+        CompendiumError::CategoryMismatch { .. } => "category_mismatch",
+        CompendiumError::UnexpectedCategory { .. } => "unexpected_category",
+        CompendiumError::UnknownCategory(_) => "unknown_category",
+        CompendiumError::IoError(_) => "io",
+        #[cfg(feature = "image")]
+        CompendiumError::ImageDecodeError(_) => "image_decode",
+        #[cfg(feature = "scraping")]
+        CompendiumError::UnexpectedHtml(_) => "unexpected_html",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_kind() {
+        assert_eq!(
+            "no_data_found",
+            error_kind(&CompendiumError::NoDataFound(String::from("entry/x")))
+        );
+        assert_eq!("server", error_kind(&CompendiumError::ServerError));
+        assert_eq!("duplicate_id", error_kind(&CompendiumError::DuplicateId(1)));
+        assert_eq!(
+            "unexpected_category",
+            error_kind(&CompendiumError::UnexpectedCategory {
+                expected: String::from("monsters"),
+                found: String::from("treasure"),
+            })
+        );
+    }
+}
@@ -31,10 +31,31 @@
     unused_qualifications
 )]
 
+#[cfg(not(any(
+    feature = "creatures",
+    feature = "equipment",
+    feature = "materials",
+    feature = "monsters",
+    feature = "treasure"
+)))]
+compile_error!("At least one of the creatures, equipment, materials, monsters or treasure features must be enabled");
+
+mod api;
+#[cfg(feature = "async")]
+pub mod async_client;
 pub mod blocking;
+pub mod cache;
+mod config;
 pub mod domain;
 mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(any(feature = "metrics", feature = "tracing"))]
+mod instrumentation;
 mod result;
+#[cfg(feature = "scraping")]
+mod scraping;
 
+pub use config::ClientConfig;
 pub use error::CompendiumError;
 pub use result::Result;
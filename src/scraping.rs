@@ -0,0 +1,157 @@
+//! A best-effort fallback that reads entries from the web compendium's HTML pages
+use crate::domain::responses::EntryResponse;
+use crate::error::CompendiumError;
+use crate::result::Result;
+use scraper::{ElementRef, Html, Selector};
+use serde_json::{json, Map, Value};
+
+/// Parse an entry page from the web compendium. The page is expected to contain the entry as
+///
+/// ```html
+/// <article class="entry" data-id="112" data-category="monsters">
+///   <h1 class="entry-name">silver moblin</h1>
+///   <img class="entry-image" src="https://botw-compendium.herokuapp.com/api/v2/entry/silver_moblin/image">
+///   <p class="entry-description">The strongest of all Moblins...</p>
+///   <ul class="entry-locations"><li>Hyrule Field</li></ul>
+///   <ul class="entry-drops"><li>moblin horn</li></ul>
+/// </article>
+/// ```
+///
+/// with `entry-hearts-recovered`, `entry-cooking-effect`, `entry-attack` and `entry-defense` elements where the category has them.
+/// Lists that are missing are treated as absent, any other missing part is an [UnexpectedHtml](crate::CompendiumError::UnexpectedHtml) error.
+pub(crate) fn parse_entry_html(html: &str) -> Result<EntryResponse> {
+    let document = Html::parse_document(html);
+    let entry = select_first(document.root_element(), "article.entry")?;
+    let attribute = |name: &str| {
+        entry
+            .value()
+            .attr(name)
+            .ok_or_else(|| unexpected_html(&format!("the entry's {} attribute", name)))
+    };
+    let id: i32 = attribute("data-id")?
+        .trim()
+        .parse()
+        .map_err(|_e| unexpected_html("a numeric entry id"))?;
+    let image = select_first(entry, ".entry-image")?
+        .value()
+        .attr("src")
+        .ok_or_else(|| unexpected_html("the entry's image src"))?;
+
+    let mut fields = Map::new();
+    fields.insert("id".into(), json!(id));
+    fields.insert("category".into(), json!(attribute("data-category")?));
+    fields.insert("name".into(), json!(select_text(entry, ".entry-name")?));
+    fields.insert("image".into(), json!(image));
+    fields.insert(
+        "description".into(),
+        json!(select_text(entry, ".entry-description")?),
+    );
+    fields.insert(
+        "common_locations".into(),
+        select_list(entry, ".entry-locations li"),
+    );
+    fields.insert("drops".into(), select_list(entry, ".entry-drops li"));
+    for (field, selector) in [
+        ("hearts_recovered", ".entry-hearts-recovered"),
+        ("cooking_effect", ".entry-cooking-effect"),
+        ("attack", ".entry-attack"),
+        ("defense", ".entry-defense"),
+    ] {
+        if let Ok(text) = select_text(entry, selector) {
+            let value = match (text.parse::<i64>(), text.parse::<f64>()) {
+                (Ok(whole), _) => json!(whole),
+                (_, Ok(fractional)) => json!(fractional),
+                _ => json!(text),
+            };
+            fields.insert(field.into(), value);
+        }
+    }
+    serde_json::from_value(Value::Object(fields))
+        .map_err(|error| unexpected_html(&format!("an entry the models accept ({})", error)))
+}
+
+fn selector(selector: &str) -> Selector {
+    Selector::parse(selector).expect("selectors are valid CSS")
+}
+
+fn select_first<'a>(element: ElementRef<'a>, css: &str) -> Result<ElementRef<'a>> {
+    element
+        .select(&selector(css))
+        .next()
+        .ok_or_else(|| unexpected_html(&format!("an element matching '{}'", css)))
+}
+
+fn select_text(element: ElementRef<'_>, css: &str) -> Result<String> {
+    select_first(element, css).map(|found| found.text().collect::<String>().trim().to_string())
+}
+
+fn select_list(element: ElementRef<'_>, css: &str) -> Value {
+    let items: Vec<String> = element
+        .select(&selector(css))
+        .map(|item| item.text().collect::<String>().trim().to_string())
+        .collect();
+    if items.is_empty() {
+        return Value::Null;
+    }
+    json!(items)
+}
+
+fn unexpected_html(expected: &str) -> CompendiumError {
+    CompendiumError::UnexpectedHtml(format!("expected {}", expected))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn silver_moblin_html<'a>() -> &'a str {
+        r#"<html><body><article class="entry" data-id="112" data-category="monsters"><h1 class="entry-name"> silver moblin </h1><img class="entry-image" src="https://botw-compendium.herokuapp.com/api/v2/entry/silver_moblin/image"><p class="entry-description">The strongest of all Moblins.</p><ul class="entry-drops"><li>moblin horn</li><li>amber</li></ul></article></body></html>"#
+    }
+
+    #[test]
+    fn test_parse_entry_html() {
+        let entry = parse_entry_html(silver_moblin_html()).unwrap();
+        match &entry {
+            EntryResponse::Monster(monster) => {
+                assert_eq!(112, monster.id());
+                assert_eq!("silver moblin", monster.name());
+                assert_eq!("The strongest of all Moblins.", monster.description());
+                assert!(monster.common_locations().is_none());
+                assert_eq!(
+                    Some(&vec![String::from("moblin horn"), String::from("amber")]),
+                    monster.drops()
+                );
+            }
+            _ => panic!("expected a monster"),
+        }
+    }
+
+    #[test]
+    fn test_parse_entry_html_equipment_stats() {
+        let html = r#"<article class="entry" data-id="347" data-category="equipment"><h1 class="entry-name">master sword</h1><img class="entry-image" src=""><p class="entry-description">The legendary sword.</p><ul class="entry-locations"><li>Hyrule Castle</li></ul><span class="entry-attack">30</span><span class="entry-defense">0</span></article>"#;
+        match parse_entry_html(html).unwrap() {
+            EntryResponse::Equipment(equipment) => {
+                assert_eq!(Some(30), equipment.attack());
+                assert_eq!(Some(0), equipment.defense());
+                assert_eq!(1, equipment.common_locations().unwrap().len());
+            }
+            _ => panic!("expected equipment"),
+        }
+    }
+
+    #[test]
+    fn test_parse_entry_html_changed_shape() {
+        let html = r#"<div class="monster"><h2>silver moblin</h2></div>"#;
+        match parse_entry_html(html) {
+            Err(CompendiumError::UnexpectedHtml(message)) => {
+                assert_eq!("expected an element matching 'article.entry'", message)
+            }
+            _ => panic!("expected an unexpected html error"),
+        }
+        let html = silver_moblin_html().replace("entry-description", "summary");
+        assert!(matches!(
+            parse_entry_html(&html),
+            Err(CompendiumError::UnexpectedHtml(_))
+        ));
+    }
+}